@@ -1,11 +1,12 @@
 use freeswitch_esl::{Esl, EslError};
+use futures::StreamExt;
 
 #[tokio::main]
 async fn main() -> Result<(), EslError> {
     let addr = "localhost:8021"; // Freeswitch host
     let password = "ClueCon";
-    let (tx, mut rx) = tokio::sync::mpsc::channel(100);
-    let inbound = Esl::inbound(addr, password, Some(tx)).await?;
+    let inbound = Esl::inbound(addr, password).await?;
+    let mut events = inbound.events();
 
     let reloadxml = inbound.api("reloadxml").await?;
     println!("reloadxml response : {:?}", reloadxml);
@@ -16,11 +17,12 @@ async fn main() -> Result<(), EslError> {
     let subscribe = inbound.subscribe(vec!["all"]).await?;
     println!("subscribe all response : {:?}", subscribe);
 
-    loop {
-        match rx.recv().await {
-            Some(ev) => println!("received event: {:#?}", ev),
-            _ => break,
-        }
+    while let Some(event) = events.next().await {
+        println!(
+            "received event {:?} for channel {:?}",
+            event.event_name(),
+            event.unique_id()
+        );
     }
     Ok(())
 }
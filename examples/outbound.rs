@@ -1,4 +1,6 @@
-use freeswitch_esl::{Esl, EslConnection, EslError};
+use std::time::Duration;
+
+use freeswitch_esl::{Esl, EslConnection, EslError, OutboundServer};
 
 async fn process_call(conn: EslConnection) -> Result<(), EslError> {
     conn.answer().await?;
@@ -25,11 +27,19 @@ async fn process_call(conn: EslConnection) -> Result<(), EslError> {
 #[tokio::main]
 async fn main() -> Result<(), EslError> {
     let addr = "0.0.0.0:8085"; // Listening address
-    println!("Listening on {}", addr);
+    println!("Listening on {}, press enter to stop", addr);
     let listener = Esl::outbound(addr).await?;
+    let server = OutboundServer::new(listener)
+        .max_sessions(100)
+        .serve(process_call);
 
-    loop {
-        let (socket, _) = listener.accept().await?;
-        tokio::spawn(async move { process_call(socket).await });
-    }
+    let _ = tokio::task::spawn_blocking(|| std::io::stdin().read_line(&mut String::new())).await;
+    println!("waiting for {} calls", server.active_sessions().len());
+    let report = server.shutdown(Duration::from_secs(30)).await;
+    println!(
+        "{} calls finished, {} calls cut",
+        report.drained.len(),
+        report.cut.len()
+    );
+    Ok(())
 }
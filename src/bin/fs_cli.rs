@@ -0,0 +1,339 @@
+//! Command line client for freeswitch, similar to `fs_cli`
+//!
+//! ```text
+//! cargo run --features cli --bin fs-cli -- -H 127.0.0.1 -p ClueCon
+//! cargo run --features cli --bin fs-cli -- -x "show channels"
+//! ```
+
+use std::io::Write;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use freeswitch_esl::{Esl, EslConnection, EslError, Event, EventFormat, LogLevel};
+use futures::StreamExt;
+use serde_json::{Map, Value};
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+const USAGE: &str = "\
+Usage: fs-cli [options]
+
+Options:
+  -H, --host <host>          host of freeswitch [default: 127.0.0.1]
+  -P, --port <port>          port of event socket [default: 8021]
+  -p, --password <password>  password of event socket [default: ClueCon]
+  -x, --execute <command>    runs api command and exits, may be repeated
+  -l, --log-level <level>    attaches to console log at given level
+  -e, --events <events>      subscribes to space separated events
+  -j, --json                 prints events as json instead of headers
+  -h, --help                 prints this help
+
+Exits with 0 on success, 1 when command fails, 2 on invalid usage and
+3 when connection with freeswitch fails.
+";
+
+const HELP: &str = "\
+Commands:
+  <command>                       runs api command
+  /bgapi <command>                runs api command in background
+  /event <plain|json|xml> <events>
+                                  subscribes to events and prints them,
+                                  json ones as json and others as headers
+  /nixevent <events>              unsubscribes from events
+  /noevents                       unsubscribes from every event
+  /log [level]                    attaches to console log, debug by default
+  /nolog                          detaches from console log
+  /help                           prints this help
+  /exit, /quit, /bye              exits
+";
+
+const COMMAND_FAILED: u8 = 1;
+const INVALID_USAGE: u8 = 2;
+const CONNECTION_FAILED: u8 = 3;
+
+#[derive(Debug)]
+struct Options {
+    host: String,
+    port: u16,
+    password: String,
+    execute: Vec<String>,
+    log_level: Option<LogLevel>,
+    events: Option<String>,
+    json: bool,
+}
+
+/// Parses command line arguments, returns `None` when help was requested
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        host: "127.0.0.1".into(),
+        port: 8021,
+        password: "ClueCon".into(),
+        execute: Vec::new(),
+        log_level: None,
+        events: None,
+        json: false,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "-H" | "--host" => options.host = value()?,
+            "-P" | "--port" => {
+                let port = value()?;
+                options.port = port.parse().map_err(|_| format!("invalid port {}", port))?;
+            }
+            "-p" | "--password" => options.password = value()?,
+            "-x" | "--execute" => options.execute.push(value()?),
+            "-l" | "--log-level" => {
+                let level = value()?;
+                options.log_level = Some(
+                    LogLevel::parse(&level)
+                        .ok_or_else(|| format!("invalid log level {}", level))?,
+                );
+            }
+            "-e" | "--events" => options.events = Some(value()?),
+            "-j" | "--json" => options.json = true,
+            "-h" | "--help" => return Ok(None),
+            _ => return Err(format!("unknown argument {}", arg)),
+        }
+    }
+    Ok(Some(options))
+}
+
+/// Returns error as shown to user, api errors carry only text of `-ERR` reply
+fn describe(error: &EslError) -> String {
+    match error {
+        EslError::ApiError(text) => text.trim().to_string(),
+        error => error.to_string(),
+    }
+}
+
+fn print_reply(reply: &Event) {
+    println!("{}", reply.header("Reply-Text").unwrap_or("+OK"));
+}
+
+fn print_event(event: &Event, json: bool) {
+    if json {
+        let mut object: Map<String, Value> = event
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        if let Some(body) = event.body() {
+            object.insert("_body".into(), Value::String(body.clone()));
+        }
+        let object = Value::Object(object);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&object).unwrap_or_default()
+        );
+        return;
+    }
+    let mut headers: Vec<_> = event.headers().iter().collect();
+    headers.sort_by_key(|(name, _)| *name);
+    println!("[EVENT] {}", event.header("Event-Name").unwrap_or_default());
+    for (name, value) in headers {
+        match value.as_str() {
+            Some(value) => println!("{}: {}", name, value),
+            None => println!("{}: {}", name, value),
+        }
+    }
+    if let Some(body) = event.body() {
+        println!("\n{}", body.trim_end());
+    }
+    println!();
+}
+
+enum Flow {
+    Continue,
+    Exit,
+}
+
+struct Repl {
+    connection: Arc<EslConnection>,
+    json: bool,
+    printing: bool,
+}
+
+impl Repl {
+    async fn run(&mut self, line: &str) -> Result<Flow, EslError> {
+        let (command, args) = match line.split_once(char::is_whitespace) {
+            Some((command, args)) => (command, args.trim()),
+            None => (line, ""),
+        };
+        match command {
+            "" => {}
+            "/exit" | "/quit" | "/bye" => return Ok(Flow::Exit),
+            "/help" => print!("{}", HELP),
+            "/event" => {
+                let mut words = args.split_whitespace();
+                let format = match words.next() {
+                    Some("plain") => EventFormat::Plain,
+                    Some("json") => EventFormat::Json,
+                    Some("xml") => EventFormat::Xml,
+                    _ => {
+                        eprintln!("usage: /event <plain|json|xml> <events>");
+                        return Ok(Flow::Continue);
+                    }
+                };
+                let events: Vec<_> = words.collect();
+                if events.is_empty() {
+                    eprintln!("usage: /event <plain|json|xml> <events>");
+                    return Ok(Flow::Continue);
+                }
+                print_reply(
+                    &self
+                        .connection
+                        .subscribe_with_format(format, events)
+                        .await?,
+                );
+                self.json = format == EventFormat::Json;
+                self.printing = true;
+            }
+            "/nixevent" => print_reply(
+                &self
+                    .connection
+                    .nixevent(args.split_whitespace().collect())
+                    .await?,
+            ),
+            "/noevents" => {
+                print_reply(&self.connection.noevents().await?);
+                self.printing = false;
+            }
+            "/log" => {
+                let level = if args.is_empty() {
+                    Some(LogLevel::Debug)
+                } else {
+                    LogLevel::parse(args)
+                };
+                let Some(level) = level else {
+                    eprintln!("invalid log level {}", args);
+                    return Ok(Flow::Continue);
+                };
+                print_reply(&self.connection.log(level).await?);
+            }
+            "/nolog" => print_reply(&self.connection.nolog().await?),
+            "/bgapi" => {
+                let connection = Arc::clone(&self.connection);
+                let command = args.to_string();
+                tokio::spawn(async move {
+                    match connection.bgapi(&command).await {
+                        Ok(output) => println!("\n[JOB] {}\n{}", command, output.trim_end()),
+                        Err(e) => eprintln!("\n[JOB] {}\n-ERR {}", command, describe(&e)),
+                    }
+                });
+                println!("+OK job started");
+            }
+            _ if command.starts_with('/') => {
+                eprintln!("unknown command {}, see /help", command)
+            }
+            _ => println!("{}", self.connection.api(line).await?.trim_end()),
+        }
+        Ok(Flow::Continue)
+    }
+}
+
+fn prompt(host: &str) {
+    print!("freeswitch@{}> ", host);
+    let _ = std::io::stdout().flush();
+}
+
+async fn interactive(connection: EslConnection, options: Options) -> ExitCode {
+    let mut repl = Repl {
+        connection: Arc::new(connection),
+        json: options.json,
+        printing: false,
+    };
+    let mut events = repl.connection.events();
+    let mut logs = repl.connection.logs();
+    if let Some(events) = &options.events {
+        let format = if options.json {
+            EventFormat::Json
+        } else {
+            EventFormat::Plain
+        };
+        let events = events.split_whitespace().collect();
+        if let Err(e) = repl.connection.subscribe_with_format(format, events).await {
+            eprintln!("-ERR {}", describe(&e));
+            return ExitCode::from(COMMAND_FAILED);
+        }
+        repl.printing = true;
+    }
+    if let Some(level) = options.log_level {
+        if let Err(e) = repl.connection.log(level).await {
+            eprintln!("-ERR {}", describe(&e));
+            return ExitCode::from(COMMAND_FAILED);
+        }
+    }
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    prompt(&options.host);
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let line = match line {
+                    Ok(Some(line)) => line,
+                    Ok(None) => return ExitCode::SUCCESS,
+                    Err(e) => {
+                        eprintln!("unable to read input: {}", e);
+                        return ExitCode::from(COMMAND_FAILED);
+                    }
+                };
+                match repl.run(line.trim()).await {
+                    Ok(Flow::Exit) => return ExitCode::SUCCESS,
+                    Ok(Flow::Continue) => {}
+                    Err(e) => eprintln!("-ERR {}", describe(&e)),
+                }
+                prompt(&options.host);
+            }
+            event = events.next() => match event {
+                Some(event) if repl.printing => print_event(&event, repl.json),
+                Some(_) => {}
+                None => {
+                    eprintln!("\nconnection with freeswitch was closed");
+                    return ExitCode::from(CONNECTION_FAILED);
+                }
+            },
+            Some(line) = logs.next() => println!("{}", line.message),
+        }
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(INVALID_USAGE);
+        }
+    };
+    let addr = (options.host.as_str(), options.port);
+    let connection = match Esl::inbound(addr, &options.password).await {
+        Ok(connection) => connection,
+        Err(e) => {
+            eprintln!(
+                "unable to connect to {}:{}: {}",
+                options.host, options.port, e
+            );
+            return ExitCode::from(CONNECTION_FAILED);
+        }
+    };
+    if options.execute.is_empty() {
+        return interactive(connection, options).await;
+    }
+    for command in &options.execute {
+        match connection.api(command).await {
+            Ok(output) => println!("{}", output.trim_end()),
+            Err(e) => {
+                eprintln!("-ERR {}", describe(&e));
+                return ExitCode::from(COMMAND_FAILED);
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,44 @@
+use std::ops::Deref;
+
+use crate::{EslConnection, EventStream};
+
+#[derive(Debug)]
+/// Handle for controlling single channel over inbound connection
+///
+/// Dereferences to [`EslConnection`] bound to the channel, so dialplan tools such as
+/// `answer`, `playback`, `play_and_get_digits` and `hangup` are sent with `sendmsg <uuid>`.
+/// Dropping handle does not close the connection.
+pub struct Call {
+    connection: EslConnection,
+    uuid: String,
+}
+
+impl Call {
+    /// returns uuid of channel controlled by this handle
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    /// returns stream of events of this channel, which ends after `CHANNEL_DESTROY`
+    pub fn channel_events(&self) -> EventStream {
+        self.connection.channel_events(&self.uuid)
+    }
+}
+
+impl Deref for Call {
+    type Target = EslConnection;
+    fn deref(&self) -> &Self::Target {
+        &self.connection
+    }
+}
+
+impl EslConnection {
+    /// returns handle for controlling channel with given uuid over this connection
+    pub fn call(&self, uuid: impl ToString) -> Call {
+        let uuid = uuid.to_string();
+        let mut connection = self.handle();
+        connection.call_uuid = Some(uuid.clone());
+        connection.channel_data = Default::default();
+        Call { connection, uuid }
+    }
+}
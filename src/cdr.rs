@@ -0,0 +1,380 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::{EslConnection, EslError, Event, EventName, EventPredicate, HangupCause};
+
+/// Records waiting for sinks before hangup events are no longer read
+const CDR_BUFFER: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Leg of call record belongs to
+pub enum CdrLeg {
+    /// Originating leg, or the only leg of call
+    A,
+    /// Leg originated by A leg
+    B,
+}
+
+impl CdrLeg {
+    /// Returns `A` or `B`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CdrLeg::A => "A",
+            CdrLeg::B => "B",
+        }
+    }
+}
+
+impl fmt::Display for CdrLeg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Call detail record of single channel built from `CHANNEL_HANGUP_COMPLETE`
+pub struct Cdr {
+    /// Uuid of channel
+    pub uuid: String,
+    /// Leg of call channel belongs to
+    pub leg: CdrLeg,
+    /// Uuid of other leg of call
+    pub other_leg_uuid: Option<String>,
+    /// `inbound` or `outbound`
+    pub direction: String,
+    /// Caller id name of caller
+    pub caller_id_name: String,
+    /// Caller id number of caller
+    pub caller_id_number: String,
+    /// Dialed number
+    pub destination_number: String,
+    /// Caller id name of callee
+    pub callee_id_name: String,
+    /// Caller id number of callee
+    pub callee_id_number: String,
+    /// Time at which channel was created
+    pub start: Option<SystemTime>,
+    /// Time at which channel was answered
+    pub answer: Option<SystemTime>,
+    /// Time at which channel was hung up
+    pub end: Option<SystemTime>,
+    /// Seconds from start to end
+    pub duration: u64,
+    /// Seconds from answer to end
+    pub billsec: u64,
+    /// Cause of hangup
+    pub hangup_cause: Option<HangupCause>,
+    /// Selected channel variables, without `variable_` prefix
+    pub variables: BTreeMap<String, String>,
+}
+
+/// Parses time from `<name>_uepoch` variable, where `0` means not set
+fn uepoch(event: &Event, name: &str) -> Option<SystemTime> {
+    let micros: u64 = event.variable(&format!("{}_uepoch", name))?.parse().ok()?;
+    (micros > 0).then(|| UNIX_EPOCH + Duration::from_micros(micros))
+}
+
+fn seconds(time: Option<SystemTime>) -> Option<u64> {
+    Some(time?.duration_since(UNIX_EPOCH).ok()?.as_secs())
+}
+
+impl Cdr {
+    /// Builds record from `CHANNEL_HANGUP_COMPLETE` event, keeping given variables
+    pub fn from_event(event: &Event, variables: &[String]) -> Option<Self> {
+        if event.event_name() != Some(EventName::ChannelHangupComplete) {
+            return None;
+        }
+        let header = |name: &str| event.header(name).unwrap_or_default().to_string();
+        let counter = |name: &str| {
+            event
+                .variable(name)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_default()
+        };
+        let leg = match event.header("Other-Type") {
+            Some("originator") => CdrLeg::B,
+            _ => CdrLeg::A,
+        };
+        let other_leg_uuid = event
+            .header("Other-Leg-Unique-ID")
+            .or_else(|| event.variable("bridge_uuid"))
+            .or_else(|| event.variable("originator"))
+            .map(ToString::to_string);
+        Some(Self {
+            uuid: event.unique_id()?.to_string(),
+            leg,
+            other_leg_uuid,
+            direction: header("Call-Direction"),
+            caller_id_name: header("Caller-Caller-ID-Name"),
+            caller_id_number: header("Caller-Caller-ID-Number"),
+            destination_number: header("Caller-Destination-Number"),
+            callee_id_name: header("Caller-Callee-ID-Name"),
+            callee_id_number: header("Caller-Callee-ID-Number"),
+            start: uepoch(event, "start"),
+            answer: uepoch(event, "answer"),
+            end: uepoch(event, "end"),
+            duration: counter("duration"),
+            billsec: counter("billsec"),
+            hangup_cause: event.hangup_cause(),
+            variables: variables
+                .iter()
+                .filter_map(|name| Some((name.clone(), event.variable(name)?.to_string())))
+                .collect(),
+        })
+    }
+
+    /// returns record as json object, times are in seconds since unix epoch
+    pub fn to_json(&self) -> Value {
+        json!({
+            "uuid": self.uuid,
+            "leg": self.leg.as_str(),
+            "other_leg_uuid": self.other_leg_uuid,
+            "direction": self.direction,
+            "caller_id_name": self.caller_id_name,
+            "caller_id_number": self.caller_id_number,
+            "destination_number": self.destination_number,
+            "callee_id_name": self.callee_id_name,
+            "callee_id_number": self.callee_id_number,
+            "start": seconds(self.start),
+            "answer": seconds(self.answer),
+            "end": seconds(self.end),
+            "duration": self.duration,
+            "billsec": self.billsec,
+            "hangup_cause": self.hangup_cause.map(|cause| cause.as_str()),
+            "variables": self.variables,
+        })
+    }
+}
+
+/// Destination to which call detail records are written
+///
+/// Sinks run on a blocking thread, so they may write to files directly.
+pub trait CdrSink: Send {
+    /// writes single record
+    fn write(&mut self, cdr: &Cdr) -> Result<(), EslError>;
+}
+
+#[derive(Debug)]
+/// Writes every record as json object on its own line
+pub struct JsonLinesSink<W> {
+    writer: W,
+}
+
+impl<W: Write> JsonLinesSink<W> {
+    /// Creates sink writing to writer, such as opened file
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+    /// Returns underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write + Send> CdrSink for JsonLinesSink<W> {
+    fn write(&mut self, cdr: &Cdr) -> Result<(), EslError> {
+        writeln!(self.writer, "{}", cdr.to_json())?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+const CSV_COLUMNS: [&str; 15] = [
+    "uuid",
+    "leg",
+    "other_leg_uuid",
+    "direction",
+    "caller_id_name",
+    "caller_id_number",
+    "destination_number",
+    "callee_id_name",
+    "callee_id_number",
+    "start",
+    "answer",
+    "end",
+    "duration",
+    "billsec",
+    "hangup_cause",
+];
+
+/// Quotes csv field when it contains separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[derive(Debug)]
+/// Writes records as csv rows, preceded by header row
+///
+/// Selected variables become extra columns after the fixed ones.
+pub struct CsvSink<W> {
+    writer: W,
+    variables: Vec<String>,
+    header_written: bool,
+}
+
+impl<W: Write> CsvSink<W> {
+    /// Creates sink writing to writer, with column for every given variable
+    pub fn new(writer: W, variables: Vec<String>) -> Self {
+        Self {
+            writer,
+            variables,
+            header_written: false,
+        }
+    }
+    /// Skips header row, for appending to existing file
+    pub fn without_header(mut self) -> Self {
+        self.header_written = true;
+        self
+    }
+    /// Returns underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write + Send> CdrSink for CsvSink<W> {
+    fn write(&mut self, cdr: &Cdr) -> Result<(), EslError> {
+        if !self.header_written {
+            let header: Vec<_> = CSV_COLUMNS
+                .iter()
+                .map(ToString::to_string)
+                .chain(self.variables.iter().map(|name| csv_field(name)))
+                .collect();
+            writeln!(self.writer, "{}", header.join(","))?;
+            self.header_written = true;
+        }
+        let time = |time| seconds(time).map(|s| s.to_string()).unwrap_or_default();
+        let mut row = vec![
+            cdr.uuid.clone(),
+            cdr.leg.to_string(),
+            cdr.other_leg_uuid.clone().unwrap_or_default(),
+            cdr.direction.clone(),
+            cdr.caller_id_name.clone(),
+            cdr.caller_id_number.clone(),
+            cdr.destination_number.clone(),
+            cdr.callee_id_name.clone(),
+            cdr.callee_id_number.clone(),
+            time(cdr.start),
+            time(cdr.answer),
+            time(cdr.end),
+            cdr.duration.to_string(),
+            cdr.billsec.to_string(),
+            cdr.hangup_cause
+                .map(|cause| cause.to_string())
+                .unwrap_or_default(),
+        ];
+        row.extend(
+            self.variables
+                .iter()
+                .map(|name| cdr.variables.get(name).cloned().unwrap_or_default()),
+        );
+        let row: Vec<_> = row.iter().map(|value| csv_field(value)).collect();
+        writeln!(self.writer, "{}", row.join(","))?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+/// Settings of call detail recording started with [`EslConnection::record_cdrs`]
+pub struct CdrConfig {
+    variables: Vec<String>,
+    sinks: Vec<Box<dyn CdrSink>>,
+}
+
+impl fmt::Debug for CdrConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CdrConfig")
+            .field("variables", &self.variables)
+            .field("sinks", &self.sinks.len())
+            .finish()
+    }
+}
+
+impl CdrConfig {
+    /// Creates config without sinks
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Keeps channel variable in every record
+    pub fn variable(mut self, name: impl ToString) -> Self {
+        self.variables.push(name.to_string());
+        self
+    }
+    /// Adds sink every record is written to
+    pub fn sink(mut self, sink: impl CdrSink + 'static) -> Self {
+        self.sinks.push(Box::new(sink));
+        self
+    }
+}
+
+impl EslConnection {
+    /// starts writing call detail record of every channel to sinks of config
+    ///
+    /// Failed writes are logged and do not stop recording.
+    pub async fn record_cdrs(&self, config: CdrConfig) -> Result<CdrRecorder, EslError> {
+        let mut events =
+            self.events_matching(EventPredicate::new().name(EventName::ChannelHangupComplete));
+        self.subscribe(vec!["CHANNEL_HANGUP_COMPLETE"]).await?;
+        let CdrConfig {
+            variables,
+            mut sinks,
+        } = config;
+        // sinks do blocking io, they run on their own thread instead of runtime workers
+        let (records_tx, mut records_rx) = mpsc::channel::<Cdr>(CDR_BUFFER);
+        let writer = tokio::task::spawn_blocking(move || {
+            while let Some(cdr) = records_rx.blocking_recv() {
+                for sink in sinks.iter_mut() {
+                    if let Err(e) = sink.write(&cdr) {
+                        warn!("failed to write cdr of {}: {}", cdr.uuid, e);
+                    }
+                }
+            }
+        });
+        let task = tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                let Some(cdr) = Cdr::from_event(&event, &variables) else {
+                    continue;
+                };
+                if records_tx.send(cdr).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(CdrRecorder { task, writer })
+    }
+}
+
+#[derive(Debug)]
+/// Running call detail recording, stops when dropped
+pub struct CdrRecorder {
+    task: JoinHandle<()>,
+    writer: JoinHandle<()>,
+}
+
+impl CdrRecorder {
+    /// waits until connection is closed and every record is written
+    pub async fn finished(mut self) {
+        let _ = (&mut self.task).await;
+        let _ = (&mut self.writer).await;
+    }
+}
+
+impl Drop for CdrRecorder {
+    fn drop(&mut self) {
+        // writer stops once records already received are written
+        self.task.abort();
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::{ChannelState, Event, HangupCause};
+
+/// Headers describing message rather than channel
+const FRAME_HEADERS: [&str; 3] = ["Content-Type", "Content-Length", "Reply-Text"];
+
+/// Prefixes of headers describing event or its source rather than channel
+const EVENT_HEADER_PREFIXES: [&str; 5] = [
+    "Event-",
+    "Core-UUID",
+    "FreeSWITCH-",
+    "Application",
+    "Job-UUID",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Data of channel controlled by outbound session
+///
+/// Built from reply to `connect` and kept up to date from `CHANNEL_DATA` and
+/// other `CHANNEL_*` events of the channel, see [`EslConnection::channel_data`](crate::EslConnection::channel_data).
+pub struct ChannelData {
+    headers: HashMap<String, String>,
+}
+
+impl ChannelData {
+    /// Builds channel data from `connect` reply or `CHANNEL_DATA` event
+    pub fn from_event(event: &Event) -> Self {
+        let mut data = Self::default();
+        data.update(event);
+        data
+    }
+
+    /// Takes latest value of every header of event
+    pub(crate) fn update(&mut self, event: &Event) {
+        for (name, value) in event.headers() {
+            if FRAME_HEADERS.contains(&name.as_str())
+                || EVENT_HEADER_PREFIXES
+                    .iter()
+                    .any(|prefix| name.starts_with(prefix))
+            {
+                continue;
+            }
+            let value = match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            self.headers.insert(name.clone(), value);
+        }
+    }
+
+    /// Returns true when event belongs to channel and carries its data
+    pub(crate) fn is_updated_by(&self, event: &Event) -> bool {
+        let channel_event = event
+            .header("Event-Name")
+            .is_some_and(|name| name.starts_with("CHANNEL_"));
+        channel_event && event.unique_id().is_some() && event.unique_id() == self.uuid()
+    }
+
+    /// Returns header value as string
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+    /// Returns every header of channel
+    pub fn headers(&self) -> &HashMap<String, String> {
+        &self.headers
+    }
+    /// Returns uuid of channel
+    pub fn uuid(&self) -> Option<&str> {
+        self.header("Unique-ID")
+            .or_else(|| self.header("Channel-Unique-ID"))
+    }
+    /// Returns `Channel-Name` such as `sofia/internal/1000@example.com`
+    pub fn channel_name(&self) -> Option<&str> {
+        self.header("Channel-Name")
+    }
+    /// Returns `Call-Direction`, `inbound` or `outbound`
+    pub fn direction(&self) -> Option<&str> {
+        self.header("Call-Direction")
+    }
+    /// Returns current state of channel state machine
+    pub fn state(&self) -> Option<ChannelState> {
+        ChannelState::parse(self.header("Channel-State")?)
+    }
+    /// Returns `Answer-State` such as `ringing` or `answered`
+    pub fn answer_state(&self) -> Option<&str> {
+        self.header("Answer-State")
+    }
+    /// Returns caller id name of caller
+    pub fn caller_id_name(&self) -> Option<&str> {
+        self.header("Caller-Caller-ID-Name")
+    }
+    /// Returns caller id number of caller
+    pub fn caller_id_number(&self) -> Option<&str> {
+        self.header("Caller-Caller-ID-Number")
+    }
+    /// Returns dialed number
+    pub fn destination_number(&self) -> Option<&str> {
+        self.header("Caller-Destination-Number")
+    }
+    /// Returns dialplan context of channel
+    pub fn context(&self) -> Option<&str> {
+        self.header("Caller-Context")
+    }
+    /// Returns dialplan type of channel, such as `XML`
+    pub fn dialplan(&self) -> Option<&str> {
+        self.header("Caller-Dialplan")
+    }
+    /// Returns cause of hangup once channel is hung up
+    pub fn hangup_cause(&self) -> Option<HangupCause> {
+        HangupCause::parse(self.header("Hangup-Cause")?)
+    }
+    /// Returns channel variable, looked up as `variable_<name>` header
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.header(&format!("variable_{}", name))
+    }
+    /// Returns every channel variable, without `variable_` prefix
+    pub fn variables(&self) -> HashMap<&str, &str> {
+        self.headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.strip_prefix("variable_")?, value.as_str())))
+            .collect()
+    }
+    /// Returns header of incoming sip request, such as `X-Account-Id`
+    pub fn sip_header(&self, name: &str) -> Option<&str> {
+        self.variable(&format!("sip_h_{}", name))
+    }
+    /// Returns every custom header of incoming sip request
+    pub fn sip_headers(&self) -> HashMap<&str, &str> {
+        self.headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((name.strip_prefix("variable_sip_h_")?, value.as_str()))
+            })
+            .collect()
+    }
+}
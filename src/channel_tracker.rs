@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures::{Stream, StreamExt};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+
+use crate::event_stream::EVENT_BUFFER;
+use crate::tracker::Tracked;
+use crate::{
+    ChannelRow, ChannelState, EslConnection, EslError, Event, EventName, EventPredicate,
+    HangupCause,
+};
+
+const CHANNEL_EVENTS: [EventName; 10] = [
+    EventName::ChannelCreate,
+    EventName::ChannelState,
+    EventName::ChannelCallstate,
+    EventName::ChannelAnswer,
+    EventName::ChannelBridge,
+    EventName::ChannelUnbridge,
+    EventName::ChannelHold,
+    EventName::ChannelUnhold,
+    EventName::ChannelHangup,
+    EventName::ChannelDestroy,
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// State of channel kept by [`ChannelTracker`]
+pub struct TrackedChannel {
+    /// Uuid of channel
+    pub uuid: String,
+    /// `inbound` or `outbound`
+    pub direction: String,
+    /// Name of channel such as `sofia/internal/1000@example.com`
+    pub name: String,
+    /// State of channel state machine
+    pub state: Option<ChannelState>,
+    /// Call state such as `RINGING`, `ACTIVE` or `HELD`
+    pub call_state: String,
+    /// Caller id name of caller
+    pub caller_id_name: String,
+    /// Caller id number of caller
+    pub caller_id_number: String,
+    /// Dialed number
+    pub destination_number: String,
+    /// Caller id name of callee
+    pub callee_id_name: String,
+    /// Caller id number of callee
+    pub callee_id_number: String,
+    /// Uuid of channel this one is bridged to
+    pub bridged_to: Option<String>,
+    /// Time at which channel was created
+    pub created: Option<SystemTime>,
+    /// Time at which channel was answered
+    pub answered: Option<SystemTime>,
+    /// Cause of hangup once channel is hung up
+    pub hangup_cause: Option<HangupCause>,
+    /// Channel variables seen on events, without `variable_` prefix
+    pub variables: HashMap<String, String>,
+}
+
+/// Parses time in microseconds, where `0` means not set
+fn micros(value: &str) -> Option<SystemTime> {
+    match value.parse::<u64>().ok()? {
+        0 => None,
+        micros => Some(UNIX_EPOCH + Duration::from_micros(micros)),
+    }
+}
+
+impl TrackedChannel {
+    fn from_row(row: ChannelRow) -> Self {
+        Self {
+            state: ChannelState::parse(&row.state),
+            created: row
+                .created_epoch
+                .parse()
+                .ok()
+                .map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+            uuid: row.uuid,
+            direction: row.direction,
+            name: row.name,
+            call_state: row.callstate,
+            caller_id_name: row.cid_name,
+            caller_id_number: row.cid_num,
+            destination_number: row.dest,
+            callee_id_name: row.callee_name,
+            callee_id_number: row.callee_num,
+            ..Default::default()
+        }
+    }
+
+    fn update(&mut self, event: &Event) {
+        let set = |field: &mut String, header: &str| {
+            if let Some(value) = event.header(header) {
+                *field = value.to_string();
+            }
+        };
+        set(&mut self.direction, "Call-Direction");
+        set(&mut self.name, "Channel-Name");
+        set(&mut self.call_state, "Channel-Call-State");
+        set(&mut self.caller_id_name, "Caller-Caller-ID-Name");
+        set(&mut self.caller_id_number, "Caller-Caller-ID-Number");
+        set(&mut self.destination_number, "Caller-Destination-Number");
+        set(&mut self.callee_id_name, "Caller-Callee-ID-Name");
+        set(&mut self.callee_id_number, "Caller-Callee-ID-Number");
+        if let Some(state) = event.channel_state() {
+            self.state = Some(state);
+        }
+        if let Some(created) = event.header("Caller-Channel-Created-Time").and_then(micros) {
+            self.created = Some(created);
+        }
+        if let Some(answered) = event
+            .header("Caller-Channel-Answered-Time")
+            .and_then(micros)
+        {
+            self.answered = Some(answered);
+        }
+        if let Some(cause) = event.hangup_cause() {
+            self.hangup_cause = Some(cause);
+        }
+        for (name, value) in event.headers() {
+            if let (Some(name), Some(value)) = (name.strip_prefix("variable_"), value.as_str()) {
+                self.variables.insert(name.to_string(), value.to_string());
+            }
+        }
+        match event.event_name() {
+            Some(EventName::ChannelCreate) if self.created.is_none() => {
+                self.created = event.timestamp();
+            }
+            Some(EventName::ChannelAnswer) if self.answered.is_none() => {
+                self.answered = event.timestamp();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Change of channel reported by [`ChannelTracker::changes`]
+pub enum ChannelChange {
+    /// Channel started being tracked
+    Created(TrackedChannel),
+    /// Channel state changed
+    Updated(TrackedChannel),
+    /// Channel was destroyed, carries its final state
+    Destroyed(TrackedChannel),
+}
+
+impl ChannelChange {
+    /// returns state of channel after change
+    pub fn channel(&self) -> &TrackedChannel {
+        match self {
+            ChannelChange::Created(channel)
+            | ChannelChange::Updated(channel)
+            | ChannelChange::Destroyed(channel) => channel,
+        }
+    }
+}
+
+/// Links or unlinks both legs of bridge
+///
+/// Freeswitch fires bridge events on A-leg only, so B-leg is updated from
+/// `Bridge-B-Unique-ID` of the same event. Returns uuids of legs which changed.
+fn apply_bridge_event<'a>(
+    channels: &mut HashMap<String, TrackedChannel>,
+    event: &'a Event,
+    uuid: &'a str,
+) -> Vec<&'a str> {
+    let bridged = match event.event_name() {
+        Some(EventName::ChannelBridge) => true,
+        Some(EventName::ChannelUnbridge) => false,
+        _ => return Vec::new(),
+    };
+    let a_leg = event.header("Bridge-A-Unique-ID").unwrap_or(uuid);
+    let b_leg = event
+        .header("Bridge-B-Unique-ID")
+        .or_else(|| event.header("Other-Leg-Unique-ID"))
+        .filter(|b_leg| *b_leg != a_leg);
+    let mut changed = Vec::new();
+    for (leg, peer) in [(Some(a_leg), b_leg), (b_leg, Some(a_leg))] {
+        let Some(channel) = leg.and_then(|leg| channels.get_mut(leg)) else {
+            continue;
+        };
+        channel.bridged_to = peer.filter(|_| bridged).map(ToString::to_string);
+        changed.extend(leg);
+    }
+    changed
+}
+
+fn apply_channel_event(
+    channels: &mut HashMap<String, TrackedChannel>,
+    event: &Event,
+) -> Vec<ChannelChange> {
+    let Some(uuid) = event.unique_id() else {
+        return Vec::new();
+    };
+    if event.event_name() == Some(EventName::ChannelDestroy) {
+        return channels
+            .remove(uuid)
+            .map(|mut channel| {
+                channel.update(event);
+                ChannelChange::Destroyed(channel)
+            })
+            .into_iter()
+            .collect();
+    }
+    let created = !channels.contains_key(uuid);
+    channels
+        .entry(uuid.to_string())
+        .or_insert_with(|| TrackedChannel {
+            uuid: uuid.to_string(),
+            ..Default::default()
+        })
+        .update(event);
+    let mut changed = vec![uuid];
+    for leg in apply_bridge_event(channels, event, uuid) {
+        if !changed.contains(&leg) {
+            changed.push(leg);
+        }
+    }
+    changed
+        .into_iter()
+        .filter_map(|leg| {
+            let channel = channels.get(leg)?.clone();
+            Some(if created && leg == uuid {
+                ChannelChange::Created(channel)
+            } else {
+                ChannelChange::Updated(channel)
+            })
+        })
+        .collect()
+}
+
+impl EslConnection {
+    /// starts tracking every channel from channel events
+    ///
+    /// When `seed` is set, channels existing before this call are loaded from
+    /// `show channels as json`.
+    pub async fn track_channels(&self, seed: bool) -> Result<ChannelTracker, EslError> {
+        let predicate = CHANNEL_EVENTS
+            .iter()
+            .cloned()
+            .fold(EventPredicate::new(), EventPredicate::name);
+        let mut events = self.events_matching(predicate);
+        let names: Vec<_> = CHANNEL_EVENTS.iter().map(EventName::as_str).collect();
+        self.subscribe(names).await?;
+        let channels = if seed {
+            self.show_channels()
+                .await?
+                .into_iter()
+                .map(|row| (row.uuid.clone(), TrackedChannel::from_row(row)))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        let (changes_tx, changes) = broadcast::channel(EVENT_BUFFER);
+        let channels = Tracked::spawn(channels, |tx| async move {
+            while let Some(event) = events.next().await {
+                let mut changes = Vec::new();
+                tx.send_if_modified(|channels| {
+                    changes = apply_channel_event(channels, &event);
+                    !changes.is_empty()
+                });
+                for change in changes {
+                    let _ = changes_tx.send(change);
+                }
+            }
+        });
+        Ok(ChannelTracker { channels, changes })
+    }
+}
+
+#[derive(Debug)]
+/// Live map of channels kept up to date by [`EslConnection::track_channels`]
+pub struct ChannelTracker {
+    channels: Tracked<HashMap<String, TrackedChannel>>,
+    changes: broadcast::Receiver<ChannelChange>,
+}
+
+impl ChannelTracker {
+    /// returns current state of every channel
+    pub fn channels(&self) -> HashMap<String, TrackedChannel> {
+        self.channels.borrow().clone()
+    }
+
+    /// returns current state of channel
+    pub fn channel(&self, uuid: &str) -> Option<TrackedChannel> {
+        self.channels.borrow().get(uuid).cloned()
+    }
+
+    /// returns number of active channels
+    pub fn len(&self) -> usize {
+        self.channels.borrow().len()
+    }
+
+    /// returns true when there are no active channels
+    pub fn is_empty(&self) -> bool {
+        self.channels.borrow().is_empty()
+    }
+
+    /// returns stream of changes made after this call
+    ///
+    /// Changes missed by lagging stream are skipped, stream ends when connection is closed.
+    pub fn changes(&self) -> impl Stream<Item = ChannelChange> + Unpin + Send {
+        tokio_stream::StreamExt::filter_map(
+            BroadcastStream::new(self.changes.resubscribe()),
+            Result::ok,
+        )
+    }
+
+    /// waits until any channel is created, updated or destroyed
+    pub async fn changed(&mut self) -> Result<(), EslError> {
+        self.channels.changed().await
+    }
+}
@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use futures::StreamExt;
+use serde_json::Value;
+
+use crate::tracker::Tracked;
+use crate::{EslConnection, EslError, Event, EventPredicate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Members of conference targeted by command
+pub enum MemberTarget {
+    /// Member with given id
+    Id(u32),
+    /// Every member
+    All,
+    /// Member which joined last
+    Last,
+    /// Every member which is not moderator
+    NonModerator,
+}
+
+impl From<u32> for MemberTarget {
+    fn from(id: u32) -> Self {
+        MemberTarget::Id(id)
+    }
+}
+
+impl fmt::Display for MemberTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemberTarget::Id(id) => write!(f, "{}", id),
+            MemberTarget::All => f.write_str("all"),
+            MemberTarget::Last => f.write_str("last"),
+            MemberTarget::NonModerator => f.write_str("non_moderator"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Member of conference
+pub struct ConferenceMember {
+    /// Id of member within conference
+    pub id: u32,
+    /// Uuid of member channel
+    pub uuid: String,
+    /// Caller id name of member
+    pub caller_id_name: String,
+    /// Caller id number of member
+    pub caller_id_number: String,
+    /// Whether member is moderator
+    pub moderator: bool,
+    /// Whether member hears conference, false when deaf
+    pub can_hear: bool,
+    /// Whether member is heard in conference, false when muted
+    pub can_speak: bool,
+    /// Whether member is talking right now
+    pub talking: bool,
+    /// Whether member has the floor
+    pub has_floor: bool,
+    /// Energy level above which member is considered talking
+    pub energy_level: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// State of conference room
+pub struct ConferenceState {
+    /// Name of conference
+    pub name: String,
+    /// Whether conference is locked for new members
+    pub locked: bool,
+    /// Members keyed by their id
+    pub members: HashMap<u32, ConferenceMember>,
+}
+
+impl ConferenceState {
+    /// returns members which are talking right now
+    pub fn talking(&self) -> Vec<&ConferenceMember> {
+        self.members
+            .values()
+            .filter(|member| member.talking)
+            .collect()
+    }
+}
+
+fn parse_member(member: &Value) -> Option<ConferenceMember> {
+    let text = |key: &str| {
+        member
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let flag = |key: &str| {
+        member
+            .pointer(&format!("/flags/{}", key))
+            .and_then(Value::as_bool)
+            .unwrap_or_default()
+    };
+    Some(ConferenceMember {
+        id: member.get("id")?.as_u64()? as u32,
+        uuid: text("uuid"),
+        caller_id_name: text("caller_id_name"),
+        caller_id_number: text("caller_id_number"),
+        moderator: flag("is_moderator"),
+        can_hear: flag("can_hear"),
+        can_speak: flag("can_speak"),
+        talking: flag("talking"),
+        has_floor: flag("has_floor"),
+        energy_level: member
+            .get("energy")
+            .and_then(Value::as_i64)
+            .unwrap_or_default(),
+    })
+}
+
+/// Parses output of `conference json_list` or `conference <name> json_list`
+pub fn parse_conference_list(output: &str) -> Result<Vec<ConferenceState>, EslError> {
+    let output = output.trim();
+    if !output.starts_with('[') {
+        // `No active conferences.`
+        return Ok(Vec::new());
+    }
+    let conferences: Value = serde_json::from_str(output)
+        .map_err(|e| EslError::ProtocolError(format!("invalid conference list: {}", e)))?;
+    let conferences = conferences.as_array().into_iter().flatten();
+    Ok(conferences
+        .map(|conference| ConferenceState {
+            name: conference
+                .get("conference_name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            locked: conference
+                .get("locked")
+                .and_then(Value::as_bool)
+                .unwrap_or_default(),
+            members: conference
+                .get("members")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(parse_member)
+                .map(|member| (member.id, member))
+                .collect(),
+        })
+        .collect())
+}
+
+#[derive(Debug)]
+/// Handle for controlling mod_conference room over connection
+pub struct Conference {
+    connection: EslConnection,
+    name: String,
+}
+
+impl Conference {
+    /// returns name of conference
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn command(&self, command: &str) -> Result<String, EslError> {
+        self.connection
+            .api(&format!("conference {} {}", self.name, command))
+            .await
+    }
+
+    /// returns current state of conference with its members from `json_list`
+    pub async fn list(&self) -> Result<ConferenceState, EslError> {
+        let output = self.command("json_list").await?;
+        parse_conference_list(&output)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| EslError::ApiError(output.trim().to_string()))
+    }
+
+    /// mutes members
+    pub async fn mute(&self, target: impl Into<MemberTarget>) -> Result<String, EslError> {
+        self.command(&format!("mute {}", target.into())).await
+    }
+
+    /// unmutes members
+    pub async fn unmute(&self, target: impl Into<MemberTarget>) -> Result<String, EslError> {
+        self.command(&format!("unmute {}", target.into())).await
+    }
+
+    /// stops members from hearing conference
+    pub async fn deaf(&self, target: impl Into<MemberTarget>) -> Result<String, EslError> {
+        self.command(&format!("deaf {}", target.into())).await
+    }
+
+    /// lets members hear conference again
+    pub async fn undeaf(&self, target: impl Into<MemberTarget>) -> Result<String, EslError> {
+        self.command(&format!("undeaf {}", target.into())).await
+    }
+
+    /// kicks members out of conference
+    pub async fn kick(&self, target: impl Into<MemberTarget>) -> Result<String, EslError> {
+        self.command(&format!("kick {}", target.into())).await
+    }
+
+    /// plays file to whole conference
+    pub async fn play(&self, file: &str) -> Result<String, EslError> {
+        self.command(&format!("play {}", file)).await
+    }
+
+    /// plays file to single member
+    pub async fn play_to(&self, file: &str, member_id: u32) -> Result<String, EslError> {
+        self.command(&format!("play {} {}", file, member_id)).await
+    }
+
+    /// starts recording conference to file
+    pub async fn record(&self, path: &str) -> Result<String, EslError> {
+        self.command(&format!("record {}", path)).await
+    }
+
+    /// stops recording to file, or every recording when `None`
+    pub async fn norecord(&self, path: Option<&str>) -> Result<String, EslError> {
+        self.command(&format!("norecord {}", path.unwrap_or("all")))
+            .await
+    }
+
+    /// locks conference so that no new members can join
+    pub async fn lock(&self) -> Result<String, EslError> {
+        self.command("lock").await
+    }
+
+    /// unlocks conference
+    pub async fn unlock(&self) -> Result<String, EslError> {
+        self.command("unlock").await
+    }
+
+    /// gives floor to member
+    pub async fn floor(&self, member_id: u32) -> Result<String, EslError> {
+        self.command(&format!("floor {}", member_id)).await
+    }
+}
+
+impl EslConnection {
+    /// returns handle for controlling conference with given name
+    pub fn conference(&self, name: impl ToString) -> Conference {
+        Conference {
+            connection: self.handle(),
+            name: name.to_string(),
+        }
+    }
+
+    /// starts tracking members of every conference from `conference::maintenance` events
+    ///
+    /// Tracker is seeded from `conference json_list`.
+    pub async fn track_conferences(&self) -> Result<ConferenceTracker, EslError> {
+        let mut events =
+            self.events_matching(EventPredicate::new().subclass("conference::maintenance"));
+        self.subscribe(vec!["CUSTOM", "conference::maintenance"])
+            .await?;
+        let conferences = parse_conference_list(&self.api("conference json_list").await?)?
+            .into_iter()
+            .map(|conference| (conference.name.clone(), conference))
+            .collect();
+        let conferences = Tracked::spawn(conferences, |tx| async move {
+            while let Some(event) = events.next().await {
+                tx.send_modify(|conferences| apply_conference_event(conferences, &event));
+            }
+        });
+        Ok(ConferenceTracker { conferences })
+    }
+}
+
+fn bool_header(event: &Event, name: &str) -> Option<bool> {
+    event.header(name).map(|value| value == "true")
+}
+
+fn update_member(member: &mut ConferenceMember, event: &Event) {
+    if let Some(uuid) = event.unique_id() {
+        member.uuid = uuid.to_string();
+    }
+    if let Some(name) = event.caller_id_name() {
+        member.caller_id_name = name.to_string();
+    }
+    if let Some(number) = event.caller_id_number() {
+        member.caller_id_number = number.to_string();
+    }
+    if let Some(member_type) = event.header("Member-Type") {
+        member.moderator = member_type == "moderator";
+    }
+    if let Some(hear) = bool_header(event, "Hear") {
+        member.can_hear = hear;
+    }
+    if let Some(speak) = bool_header(event, "Speak") {
+        member.can_speak = speak;
+    }
+    if let Some(talking) = bool_header(event, "Talking") {
+        member.talking = talking;
+    }
+    if let Some(floor) = bool_header(event, "Floor") {
+        member.has_floor = floor;
+    }
+    if let Some(level) = event
+        .header("New-Level")
+        .or_else(|| event.header("Energy-Level"))
+        .and_then(|level| level.parse().ok())
+    {
+        member.energy_level = level;
+    }
+}
+
+fn apply_conference_event(conferences: &mut HashMap<String, ConferenceState>, event: &Event) {
+    let (Some(name), Some(action)) = (event.header("Conference-Name"), event.header("Action"))
+    else {
+        return;
+    };
+    if action == "conference-destroy" {
+        conferences.remove(name);
+        return;
+    }
+    let conference = conferences
+        .entry(name.to_string())
+        .or_insert_with(|| ConferenceState {
+            name: name.to_string(),
+            ..Default::default()
+        });
+    match action {
+        "lock" => conference.locked = true,
+        "unlock" => conference.locked = false,
+        "floor-change" => {
+            let new_id = event.header("New-ID").and_then(|id| id.parse::<u32>().ok());
+            for member in conference.members.values_mut() {
+                member.has_floor = Some(member.id) == new_id;
+            }
+            return;
+        }
+        _ => {}
+    }
+    let Some(id) = event
+        .header("Member-ID")
+        .and_then(|id| id.parse::<u32>().ok())
+    else {
+        return;
+    };
+    if action == "del-member" || action == "kick-member" {
+        conference.members.remove(&id);
+        return;
+    }
+    let member = conference
+        .members
+        .entry(id)
+        .or_insert_with(|| ConferenceMember {
+            id,
+            ..Default::default()
+        });
+    update_member(member, event);
+}
+
+#[derive(Debug)]
+/// Live state of conferences kept up to date by [`EslConnection::track_conferences`]
+pub struct ConferenceTracker {
+    conferences: Tracked<HashMap<String, ConferenceState>>,
+}
+
+impl ConferenceTracker {
+    /// returns current state of every conference
+    pub fn conferences(&self) -> HashMap<String, ConferenceState> {
+        self.conferences.borrow().clone()
+    }
+
+    /// returns current state of conference
+    pub fn conference(&self, name: &str) -> Option<ConferenceState> {
+        self.conferences.borrow().get(name).cloned()
+    }
+
+    /// waits until member joins, leaves or changes in any conference
+    pub async fn changed(&mut self) -> Result<(), EslError> {
+        self.conferences.changed().await
+    }
+}
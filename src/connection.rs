@@ -1,157 +1,288 @@
+use crate::channel_data::ChannelData;
 use crate::code::{Code, ParseCode};
 use crate::error::EslError;
 use crate::esl::EslConnectionType;
-use crate::event::Event;
-use crate::io::EslCodec;
+use crate::event::{Event, EventFormat};
+use crate::event_name::EventName;
+use crate::event_stream::EVENT_BUFFER;
+use crate::execute::ExecuteHandle;
+use crate::filter::Subscriptions;
+use crate::hangup_cause::HangupCause;
+use crate::io::{decode_connect_reply, find_raw_header, parse_event, EslCodec, Frame};
+use crate::outbound::OutboundConfig;
+use crate::sendmsg::SendMsg;
 use futures::SinkExt;
-use serde_json::Value;
 use std::collections::{HashMap, VecDeque};
+use std::future::Future;
 use std::sync::atomic::Ordering;
 use std::sync::{atomic::AtomicBool, Arc};
-use tokio::io::WriteHalf;
+use std::time::Duration;
+use tokio::io::{ReadHalf, WriteHalf};
 use tokio::net::{TcpStream, ToSocketAddrs};
 use tokio::sync::{
-    oneshot::{channel, Sender},
-    mpsc,
-    Mutex,
+    broadcast,
+    oneshot::{self, channel, Sender},
+    watch, Mutex,
 };
 use tokio_stream::StreamExt;
 use tokio_util::codec::{FramedRead, FramedWrite};
-use tracing::trace;
+use tracing::{trace, warn};
+
+pub(crate) type TransportRx = FramedRead<ReadHalf<TcpStream>, EslCodec>;
+pub(crate) type TransportTx = FramedWrite<WriteHalf<TcpStream>, EslCodec>;
+pub(crate) type Reply = Sender<Result<Event, EslError>>;
+
+#[derive(Debug)]
+/// Waiter of background job or executed application
+pub(crate) struct BackgroundJob {
+    reply: Reply,
+    /// Events still expected, application run with `loops` completes once per loop
+    remaining: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// State of connection with freeswitch
+pub enum ConnectionState {
+    /// Connection is established
+    Connected,
+    /// Connection was lost and is being re-established
+    Reconnecting {
+        /// Number of current reconnect attempt, starting from 1
+        attempt: u32,
+    },
+    /// Connection is closed and will not be re-established
+    Disconnected,
+}
+
 #[derive(Debug)]
 /// contains Esl connection with freeswitch
 pub struct EslConnection {
-    password: String,
-    commands: Arc<Mutex<VecDeque<Sender<Event>>>>,
-    transport_tx: Arc<Mutex<FramedWrite<WriteHalf<TcpStream>, EslCodec>>>,
-    background_jobs: Arc<Mutex<HashMap<String, Sender<Event>>>>,
-    connected: AtomicBool,
+    pub(crate) password: String,
+    commands: Arc<Mutex<VecDeque<Reply>>>,
+    pub(crate) transport_tx: Arc<Mutex<TransportTx>>,
+    background_jobs: Arc<Mutex<HashMap<String, BackgroundJob>>>,
+    pub(crate) connected: Arc<AtomicBool>,
+    lingering: Arc<AtomicBool>,
+    default_timeout: Arc<std::sync::Mutex<Option<Duration>>>,
+    pub(crate) state: Arc<watch::Sender<ConnectionState>>,
+    pub(crate) subscriptions: Arc<std::sync::Mutex<Subscriptions>>,
+    errors: broadcast::Sender<EslError>,
+    pub(crate) events: Arc<std::sync::Mutex<Option<broadcast::Sender<Event>>>>,
+    pub(crate) logs: Arc<std::sync::Mutex<Option<broadcast::Sender<Event>>>>,
+    pub(crate) shutdown: Option<oneshot::Sender<()>>,
     pub(crate) call_uuid: Option<String>,
-    connection_info: Option<HashMap<String, Value>>,
+    pub(crate) channel_data: Arc<std::sync::Mutex<Option<ChannelData>>>,
+    pub(crate) outbound: Option<OutboundConfig>,
 }
 
 impl EslConnection {
-    /// returns call uuid in outbound mode
+    /// returns uuid of call controlled by this connection, set in outbound mode
+    /// and on handles returned by [`EslConnection::call`]
     pub async fn call_uuid(&self) -> Option<String> {
         self.call_uuid.clone()
     }
     /// disconnects from freeswitch
     pub async fn disconnect(self) -> Result<(), EslError> {
-        self.send_recv(b"exit").await?;
+        self.state.send_replace(ConnectionState::Disconnected);
+        self.request(b"exit").await?;
         self.connected.store(false, Ordering::Relaxed);
         Ok(())
     }
+    /// returns data of channel controlled by outbound session, kept up to date
+    /// from its events, `None` for inbound connection
+    pub fn channel_data(&self) -> Option<ChannelData> {
+        self.channel_data.lock().unwrap().clone()
+    }
+    /// returns mode of `socket` application for outbound sessions, `None` for inbound connection
+    pub fn outbound_config(&self) -> Option<OutboundConfig> {
+        self.outbound
+    }
     /// returns status of esl connection
     pub fn connected(&self) -> bool {
         self.connected.load(Ordering::Relaxed)
     }
-    pub(crate) async fn send(&self, item: &[u8]) -> Result<(), EslError> {
-        let mut transport = self.transport_tx.lock().await;
-        transport.send(item).await
+    /// returns true once freeswitch announced linger, channel is gone but
+    /// remaining events are still delivered until socket is closed
+    pub fn lingering(&self) -> bool {
+        self.lingering.load(Ordering::Relaxed)
+    }
+    /// asks freeswitch to keep socket open after hangup to deliver remaining events
+    ///
+    /// Without timeout socket lingers until freeswitch default linger time.
+    pub async fn linger(&self, timeout: Option<u32>) -> Result<Event, EslError> {
+        match timeout {
+            Some(seconds) => {
+                self.send_recv(format!("linger {}", seconds).as_bytes())
+                    .await
+            }
+            None => self.send_recv(b"linger").await,
+        }
+    }
+    /// asks freeswitch to close socket immediately after hangup
+    pub async fn nolinger(&self) -> Result<Event, EslError> {
+        self.send_recv(b"nolinger").await
+    }
+    /// returns current state of esl connection
+    pub fn connection_state(&self) -> ConnectionState {
+        *self.state.borrow()
+    }
+    /// returns receiver notified on every change of connection state
+    pub fn state_changes(&self) -> watch::Receiver<ConnectionState> {
+        self.state.subscribe()
+    }
+    /// returns timeout applied to commands without explicit timeout
+    pub fn default_timeout(&self) -> Option<Duration> {
+        *self.default_timeout.lock().unwrap()
+    }
+    /// sets timeout applied to commands without explicit timeout, `None` waits forever
+    pub fn set_default_timeout(&self, timeout: Option<Duration>) {
+        *self.default_timeout.lock().unwrap() = timeout;
     }
     /// sends raw message to freeswitch and receives reply
     pub async fn send_recv(&self, item: &[u8]) -> Result<Event, EslError> {
-        self.send(item).await?;
+        self.send_recv_checked(item, true, self.default_timeout())
+            .await
+    }
+    /// sends raw message to freeswitch and receives reply within given timeout
+    pub async fn send_recv_with_timeout(
+        &self,
+        item: &[u8],
+        timeout: Duration,
+    ) -> Result<Event, EslError> {
+        self.send_recv_checked(item, true, Some(timeout)).await
+    }
+    /// sends message without checking connection state, used during handshake
+    pub(crate) async fn request(&self, item: &[u8]) -> Result<Event, EslError> {
+        self.send_recv_checked(item, false, self.default_timeout())
+            .await
+    }
+    async fn send_recv_checked(
+        &self,
+        item: &[u8],
+        check_state: bool,
+        timeout: Option<Duration>,
+    ) -> Result<Event, EslError> {
+        self.send_frame(item, None, check_state, timeout).await
+    }
+    /// sends command with optional body, sized by `Content-Length`, and receives reply
+    pub(crate) async fn send_frame(
+        &self,
+        item: &[u8],
+        body: Option<&[u8]>,
+        check_state: bool,
+        timeout: Option<Duration>,
+    ) -> Result<Event, EslError> {
         let (tx, rx) = channel();
-        self.commands.lock().await.push_back(tx);
-        Ok(rx.await?)
+        let item = item.to_vec();
+        let body = body.map(<[u8]>::to_vec);
+        let transport_tx = Arc::clone(&self.transport_tx);
+        let commands = Arc::clone(&self.commands);
+        let state = Arc::clone(&self.state);
+        // reply slot is queued and command written under one lock in a separate
+        // task, so dropping this future never leaves a half written command or
+        // shifts replies to other callers
+        let write = tokio::spawn(async move {
+            let mut transport = transport_tx.lock().await;
+            if check_state {
+                match *state.borrow() {
+                    ConnectionState::Connected => {}
+                    ConnectionState::Reconnecting { .. } => return Err(EslError::ConnectionLost),
+                    ConnectionState::Disconnected => return Err(EslError::Disconnected),
+                }
+            }
+            commands.lock().await.push_back(tx);
+            let frame = Frame {
+                headers: &item,
+                body: body.as_deref(),
+            };
+            if let Err(e) = transport.send(frame).await {
+                commands.lock().await.pop_back();
+                return Err(e);
+            }
+            Ok(())
+        });
+        with_timeout(timeout, async {
+            write
+                .await
+                .map_err(|e| EslError::InternalError(e.to_string()))??;
+            rx.await?
+        })
+        .await
+    }
+
+    /// returns another handle sharing state of this connection
+    pub(crate) fn handle(&self) -> Self {
+        Self {
+            password: self.password.clone(),
+            commands: Arc::clone(&self.commands),
+            transport_tx: Arc::clone(&self.transport_tx),
+            background_jobs: Arc::clone(&self.background_jobs),
+            connected: Arc::clone(&self.connected),
+            lingering: Arc::clone(&self.lingering),
+            default_timeout: Arc::clone(&self.default_timeout),
+            state: Arc::clone(&self.state),
+            subscriptions: Arc::clone(&self.subscriptions),
+            errors: self.errors.clone(),
+            events: Arc::clone(&self.events),
+            logs: Arc::clone(&self.logs),
+            shutdown: None,
+            call_uuid: self.call_uuid.clone(),
+            channel_data: Arc::clone(&self.channel_data),
+            outbound: self.outbound,
+        }
+    }
+
+    pub(crate) fn split_transport(stream: TcpStream) -> (TransportRx, TransportTx) {
+        let esl_codec = EslCodec {};
+        let (read_half, write_half) = tokio::io::split(stream);
+        (
+            FramedRead::new(read_half, esl_codec.clone()),
+            FramedWrite::new(write_half, esl_codec),
+        )
+    }
+
+    pub(crate) fn from_transport(transport_tx: TransportTx, password: impl ToString) -> Self {
+        let (state, _) = watch::channel(ConnectionState::Connected);
+        Self {
+            password: password.to_string(),
+            commands: Arc::new(Mutex::new(VecDeque::new())),
+            background_jobs: Arc::new(Mutex::new(HashMap::new())),
+            transport_tx: Arc::new(Mutex::new(transport_tx)),
+            connected: Arc::new(AtomicBool::new(false)),
+            lingering: Arc::new(AtomicBool::new(false)),
+            default_timeout: Arc::new(std::sync::Mutex::new(None)),
+            state: Arc::new(state),
+            subscriptions: Arc::new(std::sync::Mutex::new(Subscriptions::default())),
+            errors: broadcast::channel(16).0,
+            events: Arc::new(std::sync::Mutex::new(Some(
+                broadcast::channel(EVENT_BUFFER).0,
+            ))),
+            logs: Arc::new(std::sync::Mutex::new(Some(
+                broadcast::channel(EVENT_BUFFER).0,
+            ))),
+            shutdown: None,
+            call_uuid: None,
+            channel_data: Arc::new(std::sync::Mutex::new(None)),
+            outbound: None,
+        }
     }
 
     pub(crate) async fn with_tcpstream(
         stream: TcpStream,
         password: impl ToString,
         connection_type: EslConnectionType,
-        listener: Option<mpsc::Sender<HashMap<String, Value>>>,
     ) -> Result<Self, EslError> {
-        // let sender = Arc::new(sender);
-        let commands = Arc::new(Mutex::new(VecDeque::new()));
-        let inner_commands = Arc::clone(&commands);
-        let background_jobs = Arc::new(Mutex::new(HashMap::new()));
-        let inner_background_jobs = Arc::clone(&background_jobs);
-        let esl_codec = EslCodec {};
-        let (read_half, write_half) = tokio::io::split(stream);
-        let mut transport_rx = FramedRead::new(read_half, esl_codec.clone());
-        let transport_tx = Arc::new(Mutex::new(FramedWrite::new(write_half, esl_codec.clone())));
+        let (mut transport_rx, transport_tx) = Self::split_transport(stream);
         if connection_type == EslConnectionType::Inbound {
             transport_rx.next().await;
         }
-        let mut connection = Self {
-            password: password.to_string(),
-            commands,
-            background_jobs,
-            transport_tx,
-            connected: AtomicBool::new(false),
-            call_uuid: None,
-            connection_info: None,
-        };
+        let mut connection = Self::from_transport(transport_tx, password);
+        let reader = connection.handle();
         tokio::spawn(async move {
-            loop {
-                if let Some(Ok(event)) = transport_rx.next().await {
-                    if let Some(event_type) = event.headers.get("Content-Type") {
-                        match event_type.as_str().unwrap() {
-                            "text/disconnect-notice" => {
-                                trace!("got disconnect notice");
-                                return;
-                            }
-                            "text/event-json" => {
-                                trace!("got event-json");
-                                let data = event
-                                    .body()
-                                    .clone()
-                                    .expect("Unable to get body of event-json");
-
-                                let event_body = parse_json_body(&data)
-                                    .expect("Unable to parse body of event-json");
-                                let job_uuid = event_body.get("Job-UUID");
-                                if let Some(job_uuid) = job_uuid {
-                                    let job_uuid = job_uuid.as_str().unwrap();
-                                    if let Some(tx) =
-                                        inner_background_jobs.lock().await.remove(job_uuid)
-                                    {
-                                        tx.send(event)
-                                            .expect("Unable to send channel message from bgapi");
-                                    }
-                                    trace!("continued");
-                                    continue;
-                                }
-                                if let Some(application_uuid) = event_body.get("Application-UUID") {
-                                    let job_uuid = application_uuid.as_str().unwrap();
-                                    if let Some(event_name) = event_body.get("Event-Name") {
-                                        if let Some(event_name) = event_name.as_str() {
-                                            if event_name == "CHANNEL_EXECUTE_COMPLETE" {
-                                                if let Some(tx) = inner_background_jobs
-                                                    .lock()
-                                                    .await
-                                                    .remove(job_uuid)
-                                                {
-                                                    tx.send(event).expect(
-                                                        "Unable to send channel message from bgapi",
-                                                    );
-                                                }
-                                                trace!("continued");
-                                                trace!("got channel execute complete");
-                                                continue;
-                                            }
-                                        }
-                                    }
-                                }
-                                if let Some(ref listener) = listener {
-                                    if let Err(e) = listener.send(event_body).await {
-                                        trace!("got error forwarding event event to listener: {}", e);
-                                    }
-                                }
-                                continue;
-                            }
-                            _ => {
-                                trace!("got another event {:?}", event);
-                            }
-                        }
-                    }
-                    if let Some(tx) = inner_commands.lock().await.pop_front() {
-                        tx.send(event).expect("msg");
-                    }
-                }
-            }
+            let error = reader.read_events(transport_rx).await;
+            reader
+                .connection_lost(ConnectionState::Disconnected, error)
+                .await;
         });
         match connection_type {
             EslConnectionType::Inbound => {
@@ -160,60 +291,249 @@ impl EslConnection {
                 connection
                     .subscribe(vec!["BACKGROUND_JOB", "CHANNEL_EXECUTE_COMPLETE"])
                     .await?;
+                connection.connected.store(true, Ordering::Relaxed);
             }
-            EslConnectionType::Outbound => {
-                let response = connection.send_recv(b"connect").await?;
-                trace!("{:?}", response);
-                connection.connection_info = Some(response.headers().clone());
+            EslConnectionType::Outbound(config) => {
+                // nothing but connect is accepted until freeswitch replies to it
+                let connect_response =
+                    decode_connect_reply(check_reply(connection.send_recv(b"connect").await?)?);
+                trace!("{:?}", connect_response);
+                config.check_connect_reply(&connect_response)?;
+                let channel_unique_id = connect_response
+                    .header("Channel-Unique-ID")
+                    .ok_or_else(|| {
+                        EslError::ProtocolError(
+                            "Channel-Unique-ID not found in connect reply".into(),
+                        )
+                    })?
+                    .to_string();
+                *connection.channel_data.lock().unwrap() =
+                    Some(ChannelData::from_event(&connect_response));
+                connection.outbound = Some(config);
+                connection.connected.store(true, Ordering::Relaxed);
                 let response = connection
                     .subscribe(vec!["BACKGROUND_JOB", "CHANNEL_EXECUTE_COMPLETE"])
                     .await?;
                 trace!("{:?}", response);
                 let response = connection.send_recv(b"myevents").await?;
                 trace!("{:?}", response);
-                let connection_info = connection.connection_info.as_ref().unwrap();
-
-                let channel_unique_id = connection_info
-                    .get("Channel-Unique-ID")
-                    .unwrap()
-                    .as_str()
-                    .unwrap();
-                connection.call_uuid = Some(channel_unique_id.to_string());
+                connection.call_uuid = Some(channel_unique_id);
             }
         }
         Ok(connection)
     }
 
-    /// subscribes to given events
+    /// reads messages from freeswitch and dispatches them until socket is closed
+    ///
+    /// Returns error with which pending commands are failed.
+    pub(crate) async fn read_events(&self, mut transport_rx: TransportRx) -> EslError {
+        let mut disconnect_notice = false;
+        while let Some(event) = transport_rx.next().await {
+            let event = match event {
+                Ok(event) => event,
+                Err(e) => {
+                    warn!("unable to decode message from freeswitch: {}", e);
+                    self.report_error(e.clone());
+                    return e;
+                }
+            };
+            if let Some(event_type) = event.header("Content-Type") {
+                if event_type == "text/disconnect-notice" {
+                    match event.header("Content-Disposition") {
+                        Some("linger") => {
+                            trace!("got linger disconnect notice");
+                            self.lingering.store(true, Ordering::Relaxed);
+                        }
+                        _ => {
+                            trace!("got disconnect notice");
+                            disconnect_notice = true;
+                        }
+                    }
+                    continue;
+                }
+                if event_type == "log/data" {
+                    trace!("got log line");
+                    if let Some(logs) = self.logs.lock().unwrap().as_ref() {
+                        let _ = logs.send(event);
+                    }
+                    continue;
+                }
+                if let Some(format) = EventFormat::from_content_type(event_type) {
+                    trace!("got event-{}", format.as_str());
+                    if let Err(e) = self.dispatch_event(format, event).await {
+                        warn!("unable to parse event from freeswitch: {}", e);
+                        self.report_error(e);
+                    }
+                    continue;
+                }
+                trace!("got another event {:?}", event);
+            }
+            match self.commands.lock().await.pop_front() {
+                Some(tx) => {
+                    if tx.send(Ok(event)).is_err() {
+                        trace!("command reply receiver was dropped");
+                    }
+                }
+                None => trace!("got reply without pending command {:?}", event),
+            }
+        }
+        if disconnect_notice {
+            EslError::Disconnected
+        } else {
+            EslError::ConnectionLost
+        }
+    }
+
+    async fn dispatch_event(&self, format: EventFormat, event: Event) -> Result<(), EslError> {
+        let data = event
+            .body
+            .ok_or_else(|| EslError::ProtocolError("event without body".into()))?;
+        let event = match parse_event(format, &data) {
+            Ok(event) => event,
+            Err(e) => {
+                self.fail_raw_waiter(format, &data, &e).await;
+                return Err(e);
+            }
+        };
+        if let Some(channel_data) = self.channel_data.lock().unwrap().as_mut() {
+            if channel_data.is_updated_by(&event) {
+                channel_data.update(&event);
+            }
+        }
+        if let Some(events) = self.events.lock().unwrap().as_ref() {
+            // no receivers is not an error, nobody is listening for events
+            let _ = events.send(event.clone());
+        }
+        let waiter = match event.job_uuid() {
+            Some(job_uuid) => Some(job_uuid),
+            None if event.event_name() == Some(EventName::ChannelExecuteComplete) => {
+                event.header("Application-UUID")
+            }
+            None => None,
+        };
+        if let Some(waiter) = waiter {
+            let mut jobs = self.background_jobs.lock().await;
+            if let Some(job) = jobs.get_mut(waiter) {
+                job.remaining = job.remaining.saturating_sub(1);
+                if job.remaining > 0 {
+                    trace!(
+                        "background job {} expects {} more events",
+                        waiter,
+                        job.remaining
+                    );
+                    return Ok(());
+                }
+            }
+            if let Some(job) = jobs.remove(waiter) {
+                trace!("got reply for background job {}", waiter);
+                if job.reply.send(Ok(event)).is_err() {
+                    trace!("background job receiver was dropped");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// fails background job or execute waiting for event which could not be parsed
+    async fn fail_raw_waiter(&self, format: EventFormat, data: &str, error: &EslError) {
+        let waiter = match find_raw_header(format, data, "Job-UUID") {
+            Some(job_uuid) => Some(job_uuid),
+            None if find_raw_header(format, data, "Event-Name")
+                == Some(EventName::ChannelExecuteComplete.as_str()) =>
+            {
+                find_raw_header(format, data, "Application-UUID")
+            }
+            None => None,
+        };
+        if let Some(waiter) = waiter {
+            if let Some(job) = self.background_jobs.lock().await.remove(waiter) {
+                trace!("failing background job {} with malformed event", waiter);
+                let _ = job.reply.send(Err(error.clone()));
+            }
+        }
+    }
+
+    fn report_error(&self, error: EslError) {
+        // no receivers is not an error, nobody is listening for errors
+        let _ = self.errors.send(error);
+    }
+
+    /// returns receiver of errors raised while reading from freeswitch
+    ///
+    /// Only errors raised after this call are received.
+    pub fn errors(&self) -> broadcast::Receiver<EslError> {
+        self.errors.subscribe()
+    }
+
+    /// marks connection as lost and fails every pending command with given error
+    pub(crate) async fn connection_lost(&self, state: ConnectionState, error: EslError) {
+        let _transport = self.transport_tx.lock().await;
+        self.connected.store(false, Ordering::Relaxed);
+        self.state.send_replace(state);
+        for tx in self.commands.lock().await.drain(..) {
+            let _ = tx.send(Err(error.clone()));
+        }
+        for (_, job) in self.background_jobs.lock().await.drain() {
+            let _ = job.reply.send(Err(error.clone()));
+        }
+        if state == ConnectionState::Disconnected {
+            // dropping sender ends every event stream
+            self.events.lock().unwrap().take();
+            self.logs.lock().unwrap().take();
+        }
+    }
+
+    /// subscribes to given events using current event format of connection
     pub async fn subscribe(&self, events: Vec<&str>) -> Result<Event, EslError> {
-        let message = format!("event json {}", events.join(" "));
-        self.send_recv(message.as_bytes()).await
+        self.subscribe_with_format(self.event_format(), events)
+            .await
+    }
+
+    /// subscribes to given events and switches connection to given event format
+    ///
+    /// Freeswitch uses a single format per connection, so events subscribed
+    /// earlier will also be delivered in the new format.
+    pub async fn subscribe_with_format(
+        &self,
+        format: EventFormat,
+        events: Vec<&str>,
+    ) -> Result<Event, EslError> {
+        let message = format!("event {} {}", format.as_str(), events.join(" "));
+        let response = check_reply(self.send_recv(message.as_bytes()).await?)?;
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        subscriptions.format = format;
+        subscriptions.add_events(&events);
+        Ok(response)
+    }
+
+    /// returns format in which freeswitch sends events on this connection
+    pub fn event_format(&self) -> EventFormat {
+        self.subscriptions.lock().unwrap().format
+    }
+
+    /// returns events subscribed on this connection
+    pub fn subscribed_events(&self) -> Vec<String> {
+        self.subscriptions.lock().unwrap().events()
     }
 
     pub(crate) async fn new(
         socket: impl ToSocketAddrs,
         password: impl ToString,
         connection_type: EslConnectionType,
-        listener: Option<mpsc::Sender<HashMap<String, Value>>>,
     ) -> Result<Self, EslError> {
         let stream = TcpStream::connect(socket).await?;
-        Self::with_tcpstream(stream, password, connection_type, listener).await
+        Self::with_tcpstream(stream, password, connection_type).await
     }
     pub(crate) async fn auth(&self) -> Result<String, EslError> {
         let auth_response = self
-            .send_recv(format!("auth {}", self.password).as_bytes())
+            .request(format!("auth {}", self.password).as_bytes())
             .await?;
-        let auth_headers = auth_response.headers();
-        let reply_text = auth_headers.get("Reply-Text").ok_or_else(|| {
+        let reply_text = auth_response.header("Reply-Text").ok_or_else(|| {
             EslError::InternalError("Reply-Text in auth request was not found".into())
         })?;
-        let reply_text = reply_text.as_str().unwrap();
         let (code, text) = parse_api_response(reply_text)?;
         match code {
-            Code::Ok => {
-                self.connected.store(true, Ordering::Relaxed);
-                Ok(text)
-            }
+            Code::Ok => Ok(text),
             Code::Err => Err(EslError::AuthFailed),
             Code::Unknown => Err(EslError::InternalError(
                 "Got unknown code in auth request".into(),
@@ -221,36 +541,184 @@ impl EslConnection {
         }
     }
 
-    /// For hanging up call in outbound mode
+    /// For hanging up call
     pub async fn hangup(&self, reason: &str) -> Result<Event, EslError> {
         self.execute("hangup", reason).await
     }
 
     /// executes application in freeswitch
     pub async fn execute(&self, app_name: &str, app_args: &str) -> Result<Event, EslError> {
-        let event_uuid = uuid::Uuid::new_v4().to_string();
-        let (tx, rx) = channel();
-        self.background_jobs
-            .lock()
+        self.execute_timeout(app_name, app_args, self.default_timeout())
+            .await
+    }
+
+    /// executes application in freeswitch, failing if it does not complete within timeout
+    pub async fn execute_with_timeout(
+        &self,
+        app_name: &str,
+        app_args: &str,
+        timeout: Duration,
+    ) -> Result<Event, EslError> {
+        self.execute_timeout(app_name, app_args, Some(timeout))
             .await
-            .insert(event_uuid.clone(), tx);
-        let call_uuid = self.call_uuid.as_ref().unwrap().clone();
-        let command  = format!("sendmsg {}\nexecute-app-name: {}\nexecute-app-arg: {}\ncall-command: execute\nEvent-UUID: {}",call_uuid,app_name,app_args,event_uuid);
-        let response = self.send_recv(command.as_bytes()).await?;
-        trace!("inside execute {:?}", response);
-        let resp = rx.await?;
+    }
+
+    async fn execute_timeout(
+        &self,
+        app_name: &str,
+        app_args: &str,
+        timeout: Option<Duration>,
+    ) -> Result<Event, EslError> {
+        self.execute_message(SendMsg::execute(app_name, app_args), timeout)
+            .await
+    }
+
+    /// adds defaults of outbound session mode to execute message, unless message sets them
+    pub(crate) fn execute_headers(&self, message: SendMsg) -> SendMsg {
+        match self.outbound {
+            Some(config) if config.async_mode && !message.has_header("event-lock") => {
+                message.header("event-lock", "true")
+            }
+            _ => message,
+        }
+    }
+
+    /// registers waiter resolved by last of given number of events carrying job uuid
+    async fn register_job(
+        &self,
+        job_uuid: &str,
+        events: u32,
+    ) -> oneshot::Receiver<Result<Event, EslError>> {
+        let (reply, rx) = channel();
+        self.background_jobs.lock().await.insert(
+            job_uuid.to_string(),
+            BackgroundJob {
+                reply,
+                remaining: events.max(1),
+            },
+        );
+        rx
+    }
+
+    /// makes next event of job resolve its waiter, whatever number of events was expected
+    pub(crate) async fn resolve_job_on_next_event(&self, job_uuid: &str) {
+        if let Some(job) = self.background_jobs.lock().await.get_mut(job_uuid) {
+            job.remaining = 1;
+        }
+    }
+
+    /// stops waiting for job without awaiting lock, for use in `Drop`
+    pub(crate) fn forget_job(&self, job_uuid: &str) {
+        match self.background_jobs.try_lock() {
+            Ok(mut jobs) => {
+                jobs.remove(job_uuid);
+            }
+            Err(_) => {
+                let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+                    return;
+                };
+                let jobs = Arc::clone(&self.background_jobs);
+                let job_uuid = job_uuid.to_string();
+                runtime.spawn(async move {
+                    jobs.lock().await.remove(&job_uuid);
+                });
+            }
+        }
+    }
+
+    /// sends execute message and waits for its `CHANNEL_EXECUTE_COMPLETE`
+    pub(crate) async fn execute_message(
+        &self,
+        message: SendMsg,
+        timeout: Option<Duration>,
+    ) -> Result<Event, EslError> {
+        let event_uuid = uuid::Uuid::new_v4().to_string();
+        let message = self
+            .execute_headers(message)
+            .header("Event-UUID", &event_uuid);
+        let command = message.command(self.call_uuid.as_deref())?;
+        let rx = self
+            .register_job(&event_uuid, execute_completions(&message))
+            .await;
+        let result = with_timeout(timeout, async {
+            let response = self
+                .send_frame(command.as_bytes(), message.body_bytes(), true, None)
+                .await?;
+            trace!("inside execute {:?}", response);
+            check_reply(response)?;
+            rx.await?
+        })
+        .await;
+        if result.is_err() {
+            self.background_jobs.lock().await.remove(&event_uuid);
+        }
+        let resp = result?;
         trace!("got response from channel {:?}", resp);
         Ok(resp)
     }
 
-    /// answers call in outbound mode
+    /// sends execute message and returns handle receiving its `CHANNEL_EXECUTE_COMPLETE`
+    pub(crate) async fn start_execute_message(
+        &self,
+        message: SendMsg,
+    ) -> Result<ExecuteHandle, EslError> {
+        let call_uuid = self.call_uuid.clone().ok_or(EslError::NoCallUuid)?;
+        let event_uuid = uuid::Uuid::new_v4().to_string();
+        let message = self
+            .execute_headers(message)
+            .header("Event-UUID", &event_uuid);
+        let command = message.command(Some(&call_uuid))?;
+        let rx = self
+            .register_job(&event_uuid, execute_completions(&message))
+            .await;
+        let result = self
+            .send_frame(
+                command.as_bytes(),
+                message.body_bytes(),
+                true,
+                self.default_timeout(),
+            )
+            .await
+            .and_then(check_reply);
+        if let Err(e) = result {
+            self.background_jobs.lock().await.remove(&event_uuid);
+            return Err(e);
+        }
+        Ok(ExecuteHandle {
+            connection: self.handle(),
+            call_uuid,
+            event_uuid,
+            completion: rx,
+        })
+    }
+
+    /// answers call
     pub async fn answer(&self) -> Result<Event, EslError> {
         self.execute("answer", "").await
     }
 
     /// sends api command to freeswitch
     pub async fn api(&self, command: &str) -> Result<String, EslError> {
-        let response = self.send_recv(format!("api {}", command).as_bytes()).await;
+        self.api_timeout(command, self.default_timeout()).await
+    }
+
+    /// sends api command to freeswitch, failing if reply does not arrive within timeout
+    pub async fn api_with_timeout(
+        &self,
+        command: &str,
+        timeout: Duration,
+    ) -> Result<String, EslError> {
+        self.api_timeout(command, Some(timeout)).await
+    }
+
+    async fn api_timeout(
+        &self,
+        command: &str,
+        timeout: Option<Duration>,
+    ) -> Result<String, EslError> {
+        let response = self
+            .send_recv_checked(format!("api {}", command).as_bytes(), true, timeout)
+            .await;
         let event = response?;
         let body = event
             .body
@@ -259,46 +727,94 @@ impl EslConnection {
         let (code, text) = parse_api_response(&body)?;
         match code {
             Code::Ok => Ok(text),
-            Code::Err => Err(EslError::ApiError(text)),
+            Code::Err => Err(api_error(text)),
             Code::Unknown => Ok(body),
         }
     }
 
     /// sends bgapi commands to freeswitch
     pub async fn bgapi(&self, command: &str) -> Result<String, EslError> {
+        self.bgapi_timeout(command, self.default_timeout()).await
+    }
+
+    /// sends bgapi commands to freeswitch, failing if job does not complete within timeout
+    pub async fn bgapi_with_timeout(
+        &self,
+        command: &str,
+        timeout: Duration,
+    ) -> Result<String, EslError> {
+        self.bgapi_timeout(command, Some(timeout)).await
+    }
+
+    async fn bgapi_timeout(
+        &self,
+        command: &str,
+        timeout: Option<Duration>,
+    ) -> Result<String, EslError> {
         trace!("Send bgapi {}", command);
         let job_uuid = uuid::Uuid::new_v4().to_string();
-        let (tx, rx) = channel();
-        self.background_jobs
-            .lock()
-            .await
-            .insert(job_uuid.clone(), tx);
+        let rx = self.register_job(&job_uuid, 1).await;
 
-        self.send_recv(format!("bgapi {}\nJob-UUID: {}", command, job_uuid).as_bytes())
+        let result = with_timeout(timeout, async {
+            self.send_recv_checked(
+                format!("bgapi {}\nJob-UUID: {}", command, job_uuid).as_bytes(),
+                true,
+                None,
+            )
             .await?;
-
-        let resp = rx.await?;
+            rx.await?
+        })
+        .await;
+        if result.is_err() {
+            self.background_jobs.lock().await.remove(&job_uuid);
+        }
+        let resp = result?;
         let body = resp
             .body()
-            .clone()
-            .ok_or_else(|| EslError::InternalError("body was not found in event/json".into()))?;
-
-        let body_hashmap = parse_json_body(&body)?;
-
-        let mut hsmp = resp.headers().clone();
-        hsmp.extend(body_hashmap);
-        let body = hsmp
-            .get("_body")
-            .ok_or_else(|| EslError::InternalError("body was not found in event/json".into()))?;
-        let body = body.as_str().unwrap();
+            .as_deref()
+            .ok_or_else(|| EslError::InternalError("body was not found in event".into()))?;
         let (code, text) = parse_api_response(body)?;
         match code {
             Code::Ok => Ok(text),
-            Code::Err => Err(EslError::ApiError(text)),
+            Code::Err => Err(api_error(text)),
             Code::Unknown => Ok(body.to_string()),
         }
     }
 }
+async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    future: impl Future<Output = Result<T, EslError>>,
+) -> Result<T, EslError> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, future)
+            .await
+            .map_err(|_| EslError::Timeout)?,
+        None => future.await,
+    }
+}
+/// Number of `CHANNEL_EXECUTE_COMPLETE` events sent for execute message, one per loop
+fn execute_completions(message: &SendMsg) -> u32 {
+    message
+        .header_value("loops")
+        .and_then(|loops| loops.trim().parse().ok())
+        .unwrap_or(1)
+}
+/// fails with api error when command reply is `-ERR`
+pub(crate) fn check_reply(reply: Event) -> Result<Event, EslError> {
+    match reply.header("Reply-Text") {
+        Some(text) if text.starts_with("-ERR") => Err(api_error(
+            text.trim_start_matches("-ERR").trim().to_string(),
+        )),
+        _ => Ok(reply),
+    }
+}
+/// Turns text of `-ERR` reply into error, typed when it is hangup cause
+pub(crate) fn api_error(text: String) -> EslError {
+    match HangupCause::parse(text.trim()) {
+        Some(cause) => EslError::CallFailed(cause),
+        None => EslError::ApiError(text),
+    }
+}
 fn parse_api_response(body: &str) -> Result<(Code, String), EslError> {
     let space_index = body
         .find(char::is_whitespace)
@@ -314,6 +830,3 @@ fn parse_api_response(body: &str) -> Result<(Code, String), EslError> {
     let code = code.parse_code()?;
     Ok((code, text))
 }
-fn parse_json_body(body: &str) -> Result<HashMap<String, Value>, EslError> {
-    Ok(serde_json::from_str(body)?)
-}
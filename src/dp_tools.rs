@@ -1,11 +1,7 @@
-use std::collections::HashMap;
-
-use serde_json::Value;
-
-use crate::{EslConnection, EslError, Event};
+use crate::{EslConnection, EslError, Event, HangupCause};
 
 impl EslConnection {
-    /// plays file in call during outbound mode
+    /// plays file in call
     pub async fn playback(&self, file_path: &str) -> Result<Event, EslError> {
         self.execute("playback", file_path).await
     }
@@ -28,17 +24,29 @@ impl EslConnection {
             "{min} {max} {tries} {timeout} {terminators} {file} {invalid_file} {variable_name}",
         );
         let data = self.execute(app_name, &app_args).await?;
-        let body = data.body.as_ref().unwrap();
-        let body = parse_json_body(body).unwrap();
-        let result = body.get(&format!("variable_{}", variable_name));
+        let result = data.headers.get(&format!("variable_{}", variable_name));
         let Some(digit) = result else {
-            return Err(EslError::NoInput)
+            return Err(EslError::NoInput);
         };
-        let digit = digit.as_str().unwrap().to_string();
-        Ok(digit)
+        match digit.as_str() {
+            Some(digit) => Ok(digit.to_string()),
+            None => Err(EslError::ProtocolError(format!(
+                "invalid digits variable {}",
+                digit
+            ))),
+        }
     }
-}
 
-fn parse_json_body(body: &str) -> Result<HashMap<String, Value>, EslError> {
-    Ok(serde_json::from_str(body)?)
+    /// bridges call to dial string, failing with [`EslError::CallFailed`]
+    /// when other leg could not be reached
+    pub async fn bridge(&self, dial_string: &str) -> Result<Event, EslError> {
+        let event = self.execute("bridge", dial_string).await?;
+        match event
+            .variable("originate_disposition")
+            .and_then(HangupCause::parse)
+        {
+            Some(HangupCause::Success) | None => Ok(event),
+            Some(cause) => Err(EslError::CallFailed(cause)),
+        }
+    }
 }
@@ -2,6 +2,8 @@ use std::num::ParseIntError;
 
 use thiserror::Error;
 
+use crate::HangupCause;
+
 #[derive(Clone, Debug, PartialEq, Ord, PartialOrd, Eq, Hash, Error)]
 #[allow(missing_docs)]
 /// Error type for Esl
@@ -23,6 +25,27 @@ pub enum EslError {
 
     #[error("Didnt get any digits")]
     NoInput,
+
+    #[error("Connection to freeswitch was lost.")]
+    ConnectionLost,
+
+    #[error("Connection was closed by freeswitch.")]
+    Disconnected,
+
+    #[error("Timed out waiting for reply from freeswitch.")]
+    Timeout,
+
+    #[error("Invalid data received from freeswitch: {0}")]
+    ProtocolError(String),
+
+    #[error("Connection is not bound to a call, use EslConnection::call.")]
+    NoCallUuid,
+
+    #[error("Call failed with {0}.")]
+    CallFailed(HangupCause),
+
+    #[error("Invalid event: {0}")]
+    InvalidEvent(String),
 }
 
 impl From<std::io::Error> for EslError {
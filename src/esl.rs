@@ -1,12 +1,14 @@
 use tokio::net::ToSocketAddrs;
 
-use crate::{connection::EslConnection, outbound::Outbound, EslError};
-use std::collections::HashMap;
-use serde_json::Value;
+use crate::{
+    connection::EslConnection,
+    outbound::{Outbound, OutboundConfig},
+    EslError, ReconnectConfig,
+};
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum EslConnectionType {
     Inbound,
-    Outbound,
+    Outbound(OutboundConfig),
 }
 /// Esl struct with inbound and outbound method.
 pub struct Esl;
@@ -15,13 +17,36 @@ impl Esl {
     pub async fn inbound(
         addr: impl ToSocketAddrs,
         password: impl ToString,
-        listener: Option<tokio::sync::mpsc::Sender<HashMap<String, Value>>>,
     ) -> Result<EslConnection, EslError> {
-        EslConnection::new(addr, password, EslConnectionType::Inbound, listener).await
+        EslConnection::new(addr, password, EslConnectionType::Inbound).await
+    }
+
+    /// Creates new inbound connection which reconnects automatically when socket is lost
+    ///
+    /// Commands in flight while connection is down fail with [`EslError::ConnectionLost`].
+    /// After reconnecting, authentication and every subscription are restored.
+    pub async fn inbound_with_reconnect<A>(
+        addr: A,
+        password: impl ToString,
+        config: ReconnectConfig,
+    ) -> Result<EslConnection, EslError>
+    where
+        A: ToSocketAddrs + Clone + Send + Sync + 'static,
+    {
+        EslConnection::reconnecting(addr, password, config).await
     }
 
     /// Creates new server for outbound connection
     pub async fn outbound(addr: impl ToSocketAddrs) -> Result<Outbound, EslError> {
-        Outbound::bind(addr).await
+        Outbound::bind(addr, OutboundConfig::default()).await
+    }
+
+    /// Creates new server for outbound connection from `socket` application
+    /// running in given mode
+    pub async fn outbound_with_config(
+        addr: impl ToSocketAddrs,
+        config: OutboundConfig,
+    ) -> Result<Outbound, EslError> {
+        Outbound::bind(addr, config).await
     }
 }
@@ -1,7 +1,11 @@
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use serde_json::Value;
 
+use crate::event_name::{ChannelState, EventName};
+use crate::hangup_cause::HangupCause;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// Structure of event returned from freeswitch
 pub struct Event {
@@ -17,4 +21,131 @@ impl Event {
     pub fn body(&self) -> &Option<String> {
         &self.body
     }
+    /// Returns header value as string
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(Value::as_str)
+    }
+    /// Returns channel variable, looked up as `variable_<name>` header
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.header(&format!("variable_{}", name))
+    }
+    /// Returns `Event-Name` of event, `CUSTOM` events carry their subclass
+    pub fn event_name(&self) -> Option<EventName> {
+        let name = self.header("Event-Name")?;
+        match EventName::from(name) {
+            EventName::Custom(_) => Some(EventName::Custom(
+                self.subclass().unwrap_or_default().to_string(),
+            )),
+            name => Some(name),
+        }
+    }
+    /// Returns `Event-Subclass` header
+    pub fn subclass(&self) -> Option<&str> {
+        self.header("Event-Subclass")
+    }
+    /// Returns `Unique-ID` header
+    pub fn unique_id(&self) -> Option<&str> {
+        self.header("Unique-ID")
+    }
+    /// Returns `Job-UUID` header
+    pub fn job_uuid(&self) -> Option<&str> {
+        self.header("Job-UUID")
+    }
+    /// Returns `Channel-State` header
+    pub fn channel_state(&self) -> Option<ChannelState> {
+        ChannelState::parse(self.header("Channel-State")?)
+    }
+    /// Returns `Caller-Caller-ID-Number` header
+    pub fn caller_id_number(&self) -> Option<&str> {
+        self.header("Caller-Caller-ID-Number")
+    }
+    /// Returns `Caller-Caller-ID-Name` header
+    pub fn caller_id_name(&self) -> Option<&str> {
+        self.header("Caller-Caller-ID-Name")
+    }
+    /// Returns `Caller-Destination-Number` header
+    pub fn destination_number(&self) -> Option<&str> {
+        self.header("Caller-Destination-Number")
+    }
+    /// Returns cause of hangup from `Hangup-Cause` header
+    pub fn hangup_cause(&self) -> Option<HangupCause> {
+        HangupCause::parse(self.header("Hangup-Cause")?)
+    }
+    /// Returns time at which event was fired, from `Event-Date-Timestamp` header
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        let micros: u64 = self.header("Event-Date-Timestamp")?.parse().ok()?;
+        Some(UNIX_EPOCH + Duration::from_micros(micros))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Builds event fired into freeswitch with [`EslConnection::sendevent`](crate::EslConnection::sendevent)
+pub struct EventBuilder {
+    event: Event,
+}
+impl EventBuilder {
+    /// Creates event with given name, `EventName::Custom` also sets `Event-Subclass`
+    pub fn new(name: EventName) -> Self {
+        let mut builder = Self {
+            event: Event {
+                headers: HashMap::new(),
+                body: None,
+            },
+        };
+        if let Some(subclass) = name.subclass() {
+            builder = builder.header("Event-Subclass", subclass);
+        }
+        builder.header("Event-Name", name.as_str())
+    }
+    /// Creates `CUSTOM` event with given subclass
+    pub fn custom(subclass: impl ToString) -> Self {
+        Self::new(EventName::Custom(subclass.to_string()))
+    }
+    /// Sets header, replacing earlier value
+    pub fn header(mut self, name: impl ToString, value: impl ToString) -> Self {
+        self.event
+            .headers
+            .insert(name.to_string(), Value::String(value.to_string()));
+        self
+    }
+    /// Sets body, which is sent with its `Content-Length`
+    pub fn body(mut self, body: impl ToString) -> Self {
+        self.event.body = Some(body.to_string());
+        self
+    }
+    /// Returns built event
+    pub fn build(self) -> Event {
+        self.event
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+/// Format in which freeswitch sends events over the socket
+pub enum EventFormat {
+    /// `text/event-plain`, url encoded `Key: Value` lines
+    Plain,
+    /// `text/event-json`
+    #[default]
+    Json,
+    /// `text/event-xml`
+    Xml,
+}
+impl EventFormat {
+    /// Returns name of format as used in `event <format>` command
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventFormat::Plain => "plain",
+            EventFormat::Json => "json",
+            EventFormat::Xml => "xml",
+        }
+    }
+    /// Returns format for given `Content-Type` header value
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type {
+            "text/event-plain" => Some(EventFormat::Plain),
+            "text/event-json" => Some(EventFormat::Json),
+            "text/event-xml" => Some(EventFormat::Xml),
+            _ => None,
+        }
+    }
 }
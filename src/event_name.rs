@@ -0,0 +1,203 @@
+use std::fmt;
+
+macro_rules! event_names {
+    ($($variant:ident => $name:literal,)*) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        /// Name of freeswitch event as sent in `Event-Name` header
+        pub enum EventName {
+            $(
+                #[doc = concat!("`", $name, "`")]
+                $variant,
+            )*
+            /// `CUSTOM` event with its `Event-Subclass`
+            Custom(String),
+            /// Event name not known to this crate
+            Other(String),
+        }
+
+        impl EventName {
+            /// Returns name as sent in `Event-Name` header
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(EventName::$variant => $name,)*
+                    EventName::Custom(_) => "CUSTOM",
+                    EventName::Other(name) => name,
+                }
+            }
+        }
+
+        impl From<&str> for EventName {
+            fn from(name: &str) -> Self {
+                match name {
+                    $($name => EventName::$variant,)*
+                    "CUSTOM" => EventName::Custom(String::new()),
+                    other => EventName::Other(other.to_string()),
+                }
+            }
+        }
+    };
+}
+
+event_names! {
+    Clone => "CLONE",
+    ChannelCreate => "CHANNEL_CREATE",
+    ChannelDestroy => "CHANNEL_DESTROY",
+    ChannelState => "CHANNEL_STATE",
+    ChannelCallstate => "CHANNEL_CALLSTATE",
+    ChannelAnswer => "CHANNEL_ANSWER",
+    ChannelHangup => "CHANNEL_HANGUP",
+    ChannelHangupComplete => "CHANNEL_HANGUP_COMPLETE",
+    ChannelExecute => "CHANNEL_EXECUTE",
+    ChannelExecuteComplete => "CHANNEL_EXECUTE_COMPLETE",
+    ChannelHold => "CHANNEL_HOLD",
+    ChannelUnhold => "CHANNEL_UNHOLD",
+    ChannelBridge => "CHANNEL_BRIDGE",
+    ChannelUnbridge => "CHANNEL_UNBRIDGE",
+    ChannelProgress => "CHANNEL_PROGRESS",
+    ChannelProgressMedia => "CHANNEL_PROGRESS_MEDIA",
+    ChannelOutgoing => "CHANNEL_OUTGOING",
+    ChannelPark => "CHANNEL_PARK",
+    ChannelUnpark => "CHANNEL_UNPARK",
+    ChannelApplication => "CHANNEL_APPLICATION",
+    ChannelOriginate => "CHANNEL_ORIGINATE",
+    ChannelUuid => "CHANNEL_UUID",
+    Api => "API",
+    Log => "LOG",
+    InboundChan => "INBOUND_CHAN",
+    OutboundChan => "OUTBOUND_CHAN",
+    Startup => "STARTUP",
+    Shutdown => "SHUTDOWN",
+    Publish => "PUBLISH",
+    Unpublish => "UNPUBLISH",
+    Talk => "TALK",
+    Notalk => "NOTALK",
+    SessionCrash => "SESSION_CRASH",
+    ModuleLoad => "MODULE_LOAD",
+    ModuleUnload => "MODULE_UNLOAD",
+    Dtmf => "DTMF",
+    Message => "MESSAGE",
+    PresenceIn => "PRESENCE_IN",
+    NotifyIn => "NOTIFY_IN",
+    PresenceOut => "PRESENCE_OUT",
+    PresenceProbe => "PRESENCE_PROBE",
+    MessageWaiting => "MESSAGE_WAITING",
+    MessageQuery => "MESSAGE_QUERY",
+    Roster => "ROSTER",
+    Codec => "CODEC",
+    BackgroundJob => "BACKGROUND_JOB",
+    DetectedSpeech => "DETECTED_SPEECH",
+    DetectedTone => "DETECTED_TONE",
+    PrivateCommand => "PRIVATE_COMMAND",
+    Heartbeat => "HEARTBEAT",
+    Trap => "TRAP",
+    AddSchedule => "ADD_SCHEDULE",
+    DelSchedule => "DEL_SCHEDULE",
+    ExeSchedule => "EXE_SCHEDULE",
+    ReSchedule => "RE_SCHEDULE",
+    Reloadxml => "RELOADXML",
+    Notify => "NOTIFY",
+    PhoneFeature => "PHONE_FEATURE",
+    PhoneFeatureSubscribe => "PHONE_FEATURE_SUBSCRIBE",
+    SendMessage => "SEND_MESSAGE",
+    RecvMessage => "RECV_MESSAGE",
+    RequestParams => "REQUEST_PARAMS",
+    ChannelData => "CHANNEL_DATA",
+    General => "GENERAL",
+    Command => "COMMAND",
+    SessionHeartbeat => "SESSION_HEARTBEAT",
+    ClientDisconnected => "CLIENT_DISCONNECTED",
+    ServerDisconnected => "SERVER_DISCONNECTED",
+    SendInfo => "SEND_INFO",
+    RecvInfo => "RECV_INFO",
+    RecvRtcpMessage => "RECV_RTCP_MESSAGE",
+    SendRtcpMessage => "SEND_RTCP_MESSAGE",
+    CallSecure => "CALL_SECURE",
+    Nat => "NAT",
+    RecordStart => "RECORD_START",
+    RecordStop => "RECORD_STOP",
+    PlaybackStart => "PLAYBACK_START",
+    PlaybackStop => "PLAYBACK_STOP",
+    CallUpdate => "CALL_UPDATE",
+    Failure => "FAILURE",
+    SocketData => "SOCKET_DATA",
+    MediaBugStart => "MEDIA_BUG_START",
+    MediaBugStop => "MEDIA_BUG_STOP",
+    ConferenceDataQuery => "CONFERENCE_DATA_QUERY",
+    ConferenceData => "CONFERENCE_DATA",
+    CallSetupReq => "CALL_SETUP_REQ",
+    CallSetupResult => "CALL_SETUP_RESULT",
+    CallDetail => "CALL_DETAIL",
+    DeviceState => "DEVICE_STATE",
+    Text => "TEXT",
+    ShutdownRequested => "SHUTDOWN_REQUESTED",
+    All => "ALL",
+}
+
+impl EventName {
+    /// Returns subclass of `CUSTOM` event
+    pub fn subclass(&self) -> Option<&str> {
+        match self {
+            EventName::Custom(subclass) if !subclass.is_empty() => Some(subclass),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for EventName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+macro_rules! channel_states {
+    ($($variant:ident => $name:literal,)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        /// State of channel as sent in `Channel-State` header
+        pub enum ChannelState {
+            $(
+                #[doc = concat!("`", $name, "`")]
+                $variant,
+            )*
+        }
+
+        impl ChannelState {
+            /// Returns name as sent in `Channel-State` header
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(ChannelState::$variant => $name,)*
+                }
+            }
+
+            /// Parses `Channel-State` header value
+            pub fn parse(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(ChannelState::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+channel_states! {
+    New => "CS_NEW",
+    Init => "CS_INIT",
+    Routing => "CS_ROUTING",
+    SoftExecute => "CS_SOFT_EXECUTE",
+    Execute => "CS_EXECUTE",
+    ExchangeMedia => "CS_EXCHANGE_MEDIA",
+    Park => "CS_PARK",
+    ConsumeMedia => "CS_CONSUME_MEDIA",
+    Hibernate => "CS_HIBERNATE",
+    Reset => "CS_RESET",
+    Hangup => "CS_HANGUP",
+    Reporting => "CS_REPORTING",
+    Destroy => "CS_DESTROY",
+    None => "CS_NONE",
+}
+
+impl fmt::Display for ChannelState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
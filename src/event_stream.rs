@@ -0,0 +1,221 @@
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tracing::warn;
+
+use crate::connection::check_reply;
+use crate::{EslConnection, EslError, Event, EventName};
+
+/// Number of events buffered for every subscriber before it starts lagging
+pub(crate) const EVENT_BUFFER: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// What happens when subscriber falls behind by more than the event buffer
+pub enum LagPolicy {
+    /// Skip missed events and continue with the oldest buffered one
+    #[default]
+    Skip,
+    /// End the stream
+    Close,
+}
+
+type CustomPredicate = Arc<dyn Fn(&Event) -> bool + Send + Sync>;
+
+#[derive(Clone, Default)]
+/// Selects events delivered to an [`EventStream`], every condition set must match
+pub struct EventPredicate {
+    names: Vec<EventName>,
+    unique_id: Option<String>,
+    subclass: Option<String>,
+    custom: Option<CustomPredicate>,
+}
+
+impl fmt::Debug for EventPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventPredicate")
+            .field("names", &self.names)
+            .field("unique_id", &self.unique_id)
+            .field("subclass", &self.subclass)
+            .field("custom", &self.custom.is_some())
+            .finish()
+    }
+}
+
+impl EventPredicate {
+    /// Creates predicate matching every event
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Matches events with given name, may be called multiple times to match any of them
+    ///
+    /// `EventName::Custom` with empty subclass matches every `CUSTOM` event.
+    pub fn name(mut self, name: EventName) -> Self {
+        self.names.push(name);
+        self
+    }
+    /// Matches events of channel with given `Unique-ID`
+    pub fn unique_id(mut self, unique_id: impl ToString) -> Self {
+        self.unique_id = Some(unique_id.to_string());
+        self
+    }
+    /// Matches `CUSTOM` events with given `Event-Subclass`
+    pub fn subclass(mut self, subclass: impl ToString) -> Self {
+        self.subclass = Some(subclass.to_string());
+        self
+    }
+    /// Matches events for which given function returns true
+    pub fn custom(mut self, predicate: impl Fn(&Event) -> bool + Send + Sync + 'static) -> Self {
+        self.custom = Some(Arc::new(predicate));
+        self
+    }
+    /// Returns true if event satisfies every condition of predicate
+    pub fn matches(&self, event: &Event) -> bool {
+        if !self.names.is_empty() {
+            let Some(event_name) = event.event_name() else {
+                return false;
+            };
+            let matched = self.names.iter().any(|name| match (name, &event_name) {
+                (EventName::Custom(expected), EventName::Custom(_)) if expected.is_empty() => true,
+                (name, event_name) => name == event_name,
+            });
+            if !matched {
+                return false;
+            }
+        }
+        if let Some(ref unique_id) = self.unique_id {
+            if event.unique_id() != Some(unique_id.as_str()) {
+                return false;
+            }
+        }
+        if let Some(ref subclass) = self.subclass {
+            if event.subclass() != Some(subclass.as_str()) {
+                return false;
+            }
+        }
+        if let Some(ref custom) = self.custom {
+            if !custom(event) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Stream of events received on connection
+///
+/// Ends when connection is closed for good.
+pub struct EventStream {
+    inner: BroadcastStream<Event>,
+    predicate: EventPredicate,
+    lag_policy: LagPolicy,
+    until: Option<EventName>,
+    finished: bool,
+}
+
+impl fmt::Debug for EventStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventStream")
+            .field("predicate", &self.predicate)
+            .field("lag_policy", &self.lag_policy)
+            .field("until", &self.until)
+            .finish()
+    }
+}
+
+impl EventStream {
+    pub(crate) fn new(receiver: broadcast::Receiver<Event>, predicate: EventPredicate) -> Self {
+        Self {
+            inner: BroadcastStream::new(receiver),
+            predicate,
+            lag_policy: LagPolicy::default(),
+            until: None,
+            finished: false,
+        }
+    }
+    /// Sets what happens when this stream falls behind
+    pub fn lag_policy(mut self, lag_policy: LagPolicy) -> Self {
+        self.lag_policy = lag_policy;
+        self
+    }
+    /// Ends stream right after matching event with given name is delivered
+    pub fn until(mut self, name: EventName) -> Self {
+        self.until = Some(name);
+        self
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Event;
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+        loop {
+            match Pin::new(&mut self.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => {
+                    if self.predicate.matches(&event) {
+                        if self.until.is_some() && self.until == event.event_name() {
+                            self.finished = true;
+                        }
+                        return Poll::Ready(Some(event));
+                    }
+                }
+                Poll::Ready(Some(Err(BroadcastStreamRecvError::Lagged(skipped)))) => {
+                    warn!("event subscriber lagged behind, {} events skipped", skipped);
+                    if self.lag_policy == LagPolicy::Close {
+                        return Poll::Ready(None);
+                    }
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl EslConnection {
+    /// returns stream of every event received on this connection
+    pub fn events(&self) -> EventStream {
+        self.events_matching(EventPredicate::new())
+    }
+
+    /// returns stream of events received on this connection that match predicate
+    ///
+    /// Only events subscribed with [`EslConnection::subscribe`] are sent by freeswitch.
+    pub fn events_matching(&self, predicate: EventPredicate) -> EventStream {
+        let receiver = match self.events.lock().unwrap().as_ref() {
+            Some(sender) => sender.subscribe(),
+            // connection is closed, hand out stream which ends immediately
+            None => broadcast::channel(1).1,
+        };
+        EventStream::new(receiver, predicate)
+    }
+
+    /// returns stream of events of channel with given uuid, which ends after `CHANNEL_DESTROY`
+    ///
+    /// Events have to be subscribed with [`EslConnection::subscribe`], only events
+    /// received after this call are delivered.
+    pub fn channel_events(&self, uuid: &str) -> EventStream {
+        self.events_matching(EventPredicate::new().unique_id(uuid))
+            .until(EventName::ChannelDestroy)
+    }
+
+    /// restricts this connection to events of channel with given uuid using `myevents`
+    /// and returns stream of them, which ends after `CHANNEL_DESTROY`
+    ///
+    /// Affects every stream of this connection, freeswitch delivers every event of
+    /// channel without need to subscribe.
+    pub async fn myevents(&self, uuid: &str) -> Result<EventStream, EslError> {
+        let stream = self.channel_events(uuid);
+        let format = self.event_format();
+        let command = format!("myevents {} {}", uuid, format.as_str());
+        check_reply(self.send_recv(command.as_bytes()).await?)?;
+        self.subscriptions.lock().unwrap().myevents = Some(uuid.to_string());
+        Ok(stream)
+    }
+}
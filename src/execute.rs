@@ -0,0 +1,136 @@
+use tokio::sync::oneshot;
+
+use crate::{EslConnection, EslError, Event, SendMsg};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Options of application executed with [`EslConnection::execute_with_options`]
+pub struct ExecuteOptions {
+    event_lock: Option<bool>,
+    loops: Option<u32>,
+    asynchronous: bool,
+    hold_bleg: bool,
+}
+
+impl ExecuteOptions {
+    /// Creates options executing application once, like [`EslConnection::execute`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Sets `event-lock`, application runs before any other message queued for channel
+    pub fn event_lock(mut self) -> Self {
+        self.event_lock = Some(true);
+        self
+    }
+    /// Sends `event-lock: false`, overriding default of async outbound sessions
+    pub fn without_event_lock(mut self) -> Self {
+        self.event_lock = Some(false);
+        self
+    }
+    /// Sets `loops`, application is executed given number of times
+    pub fn loops(mut self, loops: u32) -> Self {
+        self.loops = Some(loops);
+        self
+    }
+    /// Sets `async`, execute returns once freeswitch accepted application
+    /// without waiting for it to complete
+    pub fn asynchronous(mut self) -> Self {
+        self.asynchronous = true;
+        self
+    }
+    /// Sets `hold-bleg`, bridged leg is kept on hold while application runs
+    pub fn hold_bleg(mut self) -> Self {
+        self.hold_bleg = true;
+        self
+    }
+
+    fn message(&self, app_name: &str, app_args: &str) -> SendMsg {
+        let mut message = SendMsg::execute(app_name, app_args);
+        if let Some(event_lock) = self.event_lock {
+            message = message.header("event-lock", event_lock);
+        }
+        if let Some(loops) = self.loops {
+            message = message.header("loops", loops);
+        }
+        if self.asynchronous {
+            message = message.header("async", "true");
+        }
+        if self.hold_bleg {
+            message = message.header("hold-bleg", "true");
+        }
+        message
+    }
+}
+
+#[derive(Debug)]
+/// Application started with [`EslConnection::start_execute`], which may be awaited later
+pub struct ExecuteHandle {
+    pub(crate) connection: EslConnection,
+    pub(crate) call_uuid: String,
+    pub(crate) event_uuid: String,
+    pub(crate) completion: oneshot::Receiver<Result<Event, EslError>>,
+}
+
+impl ExecuteHandle {
+    /// returns `Application-UUID` of `CHANNEL_EXECUTE_COMPLETE` of this application
+    pub fn application_uuid(&self) -> &str {
+        &self.event_uuid
+    }
+
+    /// waits until application completes and returns its `CHANNEL_EXECUTE_COMPLETE`
+    ///
+    /// Application started with [`ExecuteOptions::loops`] completes once per loop,
+    /// event of last loop is returned.
+    pub async fn wait(mut self) -> Result<Event, EslError> {
+        (&mut self.completion).await?
+    }
+
+    /// interrupts application and its remaining loops with `uuid_break <uuid> all`
+    ///
+    /// Other applications queued for channel are flushed as well. Interrupted
+    /// application still completes, [`ExecuteHandle::wait`] returns its event.
+    pub async fn cancel(&self) -> Result<String, EslError> {
+        self.connection
+            .resolve_job_on_next_event(&self.event_uuid)
+            .await;
+        self.connection
+            .api(&format!("uuid_break {} all", self.call_uuid))
+            .await
+    }
+}
+
+impl Drop for ExecuteHandle {
+    fn drop(&mut self) {
+        // no-op once application completed
+        self.connection.forget_job(&self.event_uuid);
+    }
+}
+
+impl EslConnection {
+    /// executes application with given options
+    ///
+    /// Waits for application to complete unless [`ExecuteOptions::asynchronous`]
+    /// is set, then command reply is returned as soon as freeswitch accepts it.
+    pub async fn execute_with_options(
+        &self,
+        app_name: &str,
+        app_args: &str,
+        options: ExecuteOptions,
+    ) -> Result<Event, EslError> {
+        let message = options.message(app_name, app_args);
+        if options.asynchronous {
+            return self.sendmsg(&self.execute_headers(message)).await;
+        }
+        self.execute_message(message, self.default_timeout()).await
+    }
+
+    /// starts application with given options and returns handle to await or cancel it
+    pub async fn start_execute(
+        &self,
+        app_name: &str,
+        app_args: &str,
+        options: ExecuteOptions,
+    ) -> Result<ExecuteHandle, EslError> {
+        self.start_execute_message(options.message(app_name, app_args))
+            .await
+    }
+}
@@ -0,0 +1,183 @@
+use crate::connection::check_reply;
+use crate::{EslConnection, EslError, Event, EventFormat, LogLevel};
+
+#[derive(Debug, Clone, Default)]
+/// Subscriptions and filters applied on connection, replayed after reconnect
+pub(crate) struct Subscriptions {
+    pub(crate) format: EventFormat,
+    events: Vec<String>,
+    subclasses: Vec<String>,
+    excluded: Vec<String>,
+    filters: Vec<(String, String)>,
+    divert_events: bool,
+    pub(crate) myevents: Option<String>,
+    pub(crate) log: Option<LogLevel>,
+}
+
+/// Splits `event` command arguments into event names and `CUSTOM` subclasses
+///
+/// Freeswitch treats every argument after `CUSTOM` as subclass.
+fn split_events<'a>(events: &[&'a str]) -> (Vec<&'a str>, Vec<&'a str>) {
+    let mut names = Vec::new();
+    let mut subclasses = Vec::new();
+    let mut custom = false;
+    for event in events.iter().flat_map(|events| events.split_whitespace()) {
+        if custom {
+            subclasses.push(event);
+        } else if event.eq_ignore_ascii_case("CUSTOM") {
+            custom = true;
+        } else {
+            names.push(event);
+        }
+    }
+    (names, subclasses)
+}
+
+fn add_unique(list: &mut Vec<String>, items: &[&str]) {
+    for item in items {
+        if !list.iter().any(|i| i == item) {
+            list.push(item.to_string());
+        }
+    }
+}
+
+impl Subscriptions {
+    pub(crate) fn add_events(&mut self, events: &[&str]) {
+        let (names, subclasses) = split_events(events);
+        self.excluded.retain(|e| !names.contains(&e.as_str()));
+        add_unique(&mut self.events, &names);
+        add_unique(&mut self.subclasses, &subclasses);
+    }
+
+    fn remove_events(&mut self, events: &[&str]) {
+        let (names, subclasses) = split_events(events);
+        if names.iter().any(|e| e.eq_ignore_ascii_case("ALL")) {
+            self.clear_events();
+            return;
+        }
+        if self.events.iter().any(|e| e.eq_ignore_ascii_case("ALL")) {
+            add_unique(&mut self.excluded, &names);
+        }
+        self.events.retain(|e| !names.contains(&e.as_str()));
+        self.subclasses
+            .retain(|s| !subclasses.contains(&s.as_str()));
+    }
+
+    fn clear_events(&mut self) {
+        self.events.clear();
+        self.subclasses.clear();
+        self.excluded.clear();
+    }
+
+    pub(crate) fn events(&self) -> Vec<String> {
+        let mut events = self.events.clone();
+        if !self.subclasses.is_empty() {
+            events.push("CUSTOM".into());
+            events.extend(self.subclasses.iter().cloned());
+        }
+        events
+    }
+
+    /// Returns commands which bring fresh connection to same state
+    pub(crate) fn restore_commands(&self) -> Vec<String> {
+        let mut commands = Vec::new();
+        let events = self.events();
+        if !events.is_empty() {
+            commands.push(format!(
+                "event {} {}",
+                self.format.as_str(),
+                events.join(" ")
+            ));
+        }
+        if !self.excluded.is_empty() {
+            commands.push(format!("nixevent {}", self.excluded.join(" ")));
+        }
+        for (header, value) in &self.filters {
+            commands.push(format!("filter {} {}", header, value));
+        }
+        if let Some(ref uuid) = self.myevents {
+            commands.push(format!("myevents {} {}", uuid, self.format.as_str()));
+        }
+        if self.divert_events {
+            commands.push("divert_events on".into());
+        }
+        if let Some(level) = self.log {
+            commands.push(format!("log {}", level.as_str()));
+        }
+        commands
+    }
+}
+
+impl EslConnection {
+    /// adds filter so that only events with given header value are received
+    ///
+    /// Multiple filters on same header are combined with OR.
+    pub async fn filter(&self, header: &str, value: &str) -> Result<Event, EslError> {
+        let response = self
+            .send_recv(format!("filter {} {}", header, value).as_bytes())
+            .await?;
+        let response = check_reply(response)?;
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let filter = (header.to_string(), value.to_string());
+        if !subscriptions.filters.contains(&filter) {
+            subscriptions.filters.push(filter);
+        }
+        Ok(response)
+    }
+
+    /// removes filter on header, only the given value or every value when `None`
+    ///
+    /// Passing `all` as header removes every filter.
+    pub async fn filter_delete(
+        &self,
+        header: &str,
+        value: Option<&str>,
+    ) -> Result<Event, EslError> {
+        let command = match value {
+            Some(value) => format!("filter delete {} {}", header, value),
+            None => format!("filter delete {}", header),
+        };
+        let response = check_reply(self.send_recv(command.as_bytes()).await?)?;
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        subscriptions.filters.retain(|(h, v)| {
+            !(header.eq_ignore_ascii_case("all")
+                || (h == header && value.is_none_or(|value| v == value)))
+        });
+        Ok(response)
+    }
+
+    /// returns filters active on this connection as header and value pairs
+    pub fn filters(&self) -> Vec<(String, String)> {
+        self.subscriptions.lock().unwrap().filters.clone()
+    }
+
+    /// unsubscribes from given events
+    pub async fn nixevent(&self, events: Vec<&str>) -> Result<Event, EslError> {
+        let response = self
+            .send_recv(format!("nixevent {}", events.join(" ")).as_bytes())
+            .await?;
+        let response = check_reply(response)?;
+        self.subscriptions.lock().unwrap().remove_events(&events);
+        Ok(response)
+    }
+
+    /// unsubscribes from every event, including those needed by `bgapi` and `execute`
+    pub async fn noevents(&self) -> Result<Event, EslError> {
+        let response = check_reply(self.send_recv(b"noevents").await?)?;
+        self.subscriptions.lock().unwrap().clear_events();
+        Ok(response)
+    }
+
+    /// redirects events of session, which would otherwise go to embedded
+    /// language input callbacks, to this connection
+    pub async fn divert_events(&self, enable: bool) -> Result<Event, EslError> {
+        let command = if enable {
+            "divert_events on"
+        } else {
+            "divert_events off"
+        };
+        let response = check_reply(self.send_recv(command.as_bytes()).await?)?;
+        self.subscriptions.lock().unwrap().divert_events = enable;
+        Ok(response)
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+
+macro_rules! hangup_causes {
+    ($($variant:ident => $name:literal = $code:literal,)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        /// Reason for which call was ended, as sent in `Hangup-Cause` header
+        pub enum HangupCause {
+            $(
+                #[doc = concat!("`", $name, "` (", $code, ")")]
+                $variant,
+            )*
+        }
+
+        impl HangupCause {
+            /// Returns name as sent in `Hangup-Cause` header
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(HangupCause::$variant => $name,)*
+                }
+            }
+
+            /// Returns Q.850 cause code, freeswitch specific causes use codes above 127
+            pub fn code(&self) -> u16 {
+                match self {
+                    $(HangupCause::$variant => $code,)*
+                }
+            }
+
+            /// Parses `Hangup-Cause` header value
+            pub fn parse(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(HangupCause::$variant),)*
+                    _ => None,
+                }
+            }
+
+            /// Returns cause with given Q.850 or freeswitch code
+            pub fn from_code(code: u16) -> Option<Self> {
+                match code {
+                    $($code => Some(HangupCause::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+hangup_causes! {
+    Unspecified => "UNSPECIFIED" = 0,
+    UnallocatedNumber => "UNALLOCATED_NUMBER" = 1,
+    NoRouteTransitNet => "NO_ROUTE_TRANSIT_NET" = 2,
+    NoRouteDestination => "NO_ROUTE_DESTINATION" = 3,
+    ChannelUnacceptable => "CHANNEL_UNACCEPTABLE" = 6,
+    CallAwardedDelivered => "CALL_AWARDED_DELIVERED" = 7,
+    NormalClearing => "NORMAL_CLEARING" = 16,
+    UserBusy => "USER_BUSY" = 17,
+    NoUserResponse => "NO_USER_RESPONSE" = 18,
+    NoAnswer => "NO_ANSWER" = 19,
+    SubscriberAbsent => "SUBSCRIBER_ABSENT" = 20,
+    CallRejected => "CALL_REJECTED" = 21,
+    NumberChanged => "NUMBER_CHANGED" = 22,
+    RedirectionToNewDestination => "REDIRECTION_TO_NEW_DESTINATION" = 23,
+    ExchangeRoutingError => "EXCHANGE_ROUTING_ERROR" = 25,
+    DestinationOutOfOrder => "DESTINATION_OUT_OF_ORDER" = 27,
+    InvalidNumberFormat => "INVALID_NUMBER_FORMAT" = 28,
+    FacilityRejected => "FACILITY_REJECTED" = 29,
+    ResponseToStatusEnquiry => "RESPONSE_TO_STATUS_ENQUIRY" = 30,
+    NormalUnspecified => "NORMAL_UNSPECIFIED" = 31,
+    NormalCircuitCongestion => "NORMAL_CIRCUIT_CONGESTION" = 34,
+    NetworkOutOfOrder => "NETWORK_OUT_OF_ORDER" = 38,
+    NormalTemporaryFailure => "NORMAL_TEMPORARY_FAILURE" = 41,
+    SwitchCongestion => "SWITCH_CONGESTION" = 42,
+    AccessInfoDiscarded => "ACCESS_INFO_DISCARDED" = 43,
+    RequestedChanUnavail => "REQUESTED_CHAN_UNAVAIL" = 44,
+    PreEmpted => "PRE_EMPTED" = 45,
+    FacilityNotSubscribed => "FACILITY_NOT_SUBSCRIBED" = 50,
+    OutgoingCallBarred => "OUTGOING_CALL_BARRED" = 52,
+    IncomingCallBarred => "INCOMING_CALL_BARRED" = 54,
+    BearercapabilityNotauth => "BEARERCAPABILITY_NOTAUTH" = 57,
+    BearercapabilityNotavail => "BEARERCAPABILITY_NOTAVAIL" = 58,
+    ServiceUnavailable => "SERVICE_UNAVAILABLE" = 63,
+    BearercapabilityNotimpl => "BEARERCAPABILITY_NOTIMPL" = 65,
+    ChanNotImplemented => "CHAN_NOT_IMPLEMENTED" = 66,
+    FacilityNotImplemented => "FACILITY_NOT_IMPLEMENTED" = 69,
+    ServiceNotImplemented => "SERVICE_NOT_IMPLEMENTED" = 79,
+    InvalidCallReference => "INVALID_CALL_REFERENCE" = 81,
+    IncompatibleDestination => "INCOMPATIBLE_DESTINATION" = 88,
+    InvalidMsgUnspecified => "INVALID_MSG_UNSPECIFIED" = 95,
+    MandatoryIeMissing => "MANDATORY_IE_MISSING" = 96,
+    MessageTypeNonexist => "MESSAGE_TYPE_NONEXIST" = 97,
+    WrongMessage => "WRONG_MESSAGE" = 98,
+    IeNonexist => "IE_NONEXIST" = 99,
+    InvalidIeContents => "INVALID_IE_CONTENTS" = 100,
+    WrongCallState => "WRONG_CALL_STATE" = 101,
+    RecoveryOnTimerExpire => "RECOVERY_ON_TIMER_EXPIRE" = 102,
+    MandatoryIeLengthError => "MANDATORY_IE_LENGTH_ERROR" = 103,
+    ProtocolError => "PROTOCOL_ERROR" = 111,
+    Interworking => "INTERWORKING" = 127,
+    Success => "SUCCESS" = 142,
+    OriginatorCancel => "ORIGINATOR_CANCEL" = 487,
+    LoseRace => "LOSE_RACE" = 502,
+    ManagerRequest => "MANAGER_REQUEST" = 503,
+    BlindTransfer => "BLIND_TRANSFER" = 600,
+    AttendedTransfer => "ATTENDED_TRANSFER" = 601,
+    AllottedTimeout => "ALLOTTED_TIMEOUT" = 602,
+    UserChallenge => "USER_CHALLENGE" = 603,
+    MediaTimeout => "MEDIA_TIMEOUT" = 604,
+    PickedOff => "PICKED_OFF" = 605,
+    UserNotRegistered => "USER_NOT_REGISTERED" = 606,
+    ProgressTimeout => "PROGRESS_TIMEOUT" = 607,
+    InvalidGateway => "INVALID_GATEWAY" = 608,
+    GatewayDown => "GATEWAY_DOWN" = 609,
+    InvalidUrl => "INVALID_URL" = 610,
+    InvalidProfile => "INVALID_PROFILE" = 611,
+    NoPickup => "NO_PICKUP" = 612,
+    SrtpReadError => "SRTP_READ_ERROR" = 613,
+    Bowout => "BOWOUT" = 614,
+    BusyEverywhere => "BUSY_EVERYWHERE" = 615,
+    Decline => "DECLINE" = 616,
+    DoesNotExistAnywhere => "DOES_NOT_EXIST_ANYWHERE" = 617,
+    NotAcceptable => "NOT_ACCEPTABLE" = 618,
+    Unwanted => "UNWANTED" = 619,
+    NoIdentity => "NO_IDENTITY" = 620,
+    BadIdentityInfo => "BAD_IDENTITY_INFO" = 621,
+    UnsupportedCertificate => "UNSUPPORTED_CERTIFICATE" = 622,
+    InvalidIdentity => "INVALID_IDENTITY" = 623,
+    StaleDate => "STALE_DATE" = 624,
+    RejectAll => "REJECT_ALL" = 625,
+    Crash => "CRASH" = 700,
+    SystemShutdown => "SYSTEM_SHUTDOWN" = 701,
+}
+
+impl fmt::Display for HangupCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
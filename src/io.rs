@@ -1,24 +1,70 @@
 use std::collections::HashMap;
 
 use bytes::Buf;
+use percent_encoding::percent_decode_str;
+use quick_xml::events::Event as XmlEvent;
 use serde_json::Value;
 use tokio_util::codec::{Decoder, Encoder};
 use tracing::trace;
 
-use crate::{event::Event, EslError};
+use crate::{
+    event::{Event, EventFormat},
+    EslError,
+};
 
 #[derive(Debug, Clone)]
 pub(crate) struct EslCodec {}
 
+/// Command with optional body, which follows blank line and is sized by `Content-Length`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Frame<'a> {
+    pub(crate) headers: &'a [u8],
+    pub(crate) body: Option<&'a [u8]>,
+}
+
+impl Encoder<Frame<'_>> for EslCodec {
+    type Error = EslError;
+    fn encode(&mut self, item: Frame<'_>, dst: &mut bytes::BytesMut) -> Result<(), Self::Error> {
+        dst.extend_from_slice(item.headers);
+        match item.body {
+            Some(body) => {
+                dst.extend_from_slice(format!("\nContent-Length: {}\n\n", body.len()).as_bytes());
+                dst.extend_from_slice(body);
+            }
+            None => dst.extend_from_slice(b"\n\n"),
+        }
+        Ok(())
+    }
+}
+
 impl Encoder<&[u8]> for EslCodec {
     type Error = EslError;
     fn encode(&mut self, item: &[u8], dst: &mut bytes::BytesMut) -> Result<(), Self::Error> {
-        dst.extend_from_slice(item);
-        dst.extend_from_slice(b"\n\n");
-        Ok(())
+        self.encode(
+            Frame {
+                headers: item,
+                body: None,
+            },
+            dst,
+        )
     }
 }
 
+/// Appends `name: value` header line to command, rejecting headers which would break framing
+pub(crate) fn push_header(command: &mut String, name: &str, value: &str) -> Result<(), EslError> {
+    if name.is_empty() || name.contains([':', '\n', '\r']) || value.contains(['\n', '\r']) {
+        return Err(EslError::InvalidEvent(format!(
+            "header {:?} can not be sent",
+            name
+        )));
+    }
+    command.push('\n');
+    command.push_str(name);
+    command.push_str(": ");
+    command.push_str(value);
+    Ok(())
+}
+
 fn get_header_end(src: &bytes::BytesMut) -> Option<usize> {
     trace!("get_header_end:=>{:?}", src);
     // get first new line character
@@ -34,44 +80,214 @@ fn parse_body(src: &[u8], length: usize) -> String {
     trace!("length src : {}", length);
     String::from_utf8_lossy(&src[..length]).to_string()
 }
-fn parse_header(src: &[u8]) -> Result<HashMap<String, Value>, std::io::Error> {
+fn parse_header(src: &[u8]) -> Result<HashMap<String, Value>, EslError> {
     trace!("parsing this header {:#?}", String::from_utf8_lossy(src));
     let data = String::from_utf8_lossy(src).to_string();
-    let a = data.split('\n');
     let mut hash = HashMap::new();
-    for line in a {
-        let mut key_value = line.split(':');
-        let key = key_value.next().unwrap().trim().to_string();
-        let val = key_value.next().unwrap().trim().to_string();
-        hash.insert(key, serde_json::json!(val));
+    for line in data.lines().filter(|line| !line.is_empty()) {
+        let (key, val) = line
+            .split_once(':')
+            .ok_or_else(|| EslError::ProtocolError(format!("invalid header line {:?}", line)))?;
+        hash.insert(key.trim().to_string(), serde_json::json!(val.trim()));
     }
     trace!("returning hashmap : {:?}", hash);
     Ok(hash)
 }
 
+/// Parses body of `text/event-*` message into event
+pub(crate) fn parse_event(format: EventFormat, body: &str) -> Result<Event, EslError> {
+    match format {
+        EventFormat::Plain => parse_plain_event(body),
+        EventFormat::Json => parse_json_event(body),
+        EventFormat::Xml => parse_xml_event(body),
+    }
+}
+
+/// Looks up header in body of event which could not be parsed, without validating the rest
+///
+/// Used to find waiter of malformed event, so that it can be failed rather than left hanging.
+pub(crate) fn find_raw_header<'a>(
+    format: EventFormat,
+    body: &'a str,
+    name: &str,
+) -> Option<&'a str> {
+    let value = match format {
+        EventFormat::Plain => {
+            body.lines()
+                .take_while(|line| !line.is_empty())
+                .find_map(|line| {
+                    let (key, value) = line.split_once(':')?;
+                    (key.trim() == name).then_some(value)
+                })?
+        }
+        EventFormat::Json => {
+            let rest = &body[body.find(&format!("\"{}\"", name))? + name.len() + 2..];
+            let rest = rest.trim_start().strip_prefix(':')?.trim_start();
+            let rest = rest.strip_prefix('"')?;
+            &rest[..rest.find('"')?]
+        }
+        EventFormat::Xml => {
+            let rest = &body[body.find(&format!("<{}>", name))? + name.len() + 2..];
+            &rest[..rest.find('<')?]
+        }
+    };
+    Some(value.trim())
+}
+
+fn url_decode(value: &str) -> String {
+    percent_decode_str(value).decode_utf8_lossy().to_string()
+}
+
+/// Decodes header values of `connect` reply, freeswitch url encodes them like plain events
+pub(crate) fn decode_connect_reply(mut reply: Event) -> Event {
+    for value in reply.headers.values_mut() {
+        if let Value::String(text) = value {
+            *text = url_decode(text);
+        }
+    }
+    reply
+}
+
+fn parse_plain_event(src: &str) -> Result<Event, EslError> {
+    trace!("parsing plain event {:?}", src);
+    let (header_part, rest) = match src.find("\n\n") {
+        Some(index) => (&src[..index], &src[(index + 2)..]),
+        None => (src, ""),
+    };
+    let mut headers = HashMap::new();
+    for line in header_part.lines().filter(|line| !line.is_empty()) {
+        let (key, value) = line.split_once(':').ok_or_else(|| {
+            EslError::ProtocolError(format!("invalid header line in plain event {:?}", line))
+        })?;
+        headers.insert(
+            key.trim().to_string(),
+            Value::from(url_decode(value.trim())),
+        );
+    }
+    let body = match headers.get("Content-Length").and_then(Value::as_str) {
+        Some(length) => {
+            let length: usize = length.parse().map_err(|_| {
+                EslError::ProtocolError(format!("invalid Content-Length {} in plain event", length))
+            })?;
+            let rest = rest.as_bytes();
+            if rest.len() < length {
+                return Err(EslError::ProtocolError(
+                    "plain event body is shorter than Content-Length".into(),
+                ));
+            }
+            Some(String::from_utf8_lossy(&rest[..length]).to_string())
+        }
+        None => None,
+    };
+    Ok(Event { headers, body })
+}
+
+fn parse_json_event(src: &str) -> Result<Event, EslError> {
+    let mut headers: HashMap<String, Value> =
+        serde_json::from_str(src).map_err(|e| EslError::ProtocolError(e.to_string()))?;
+    let body = match headers.remove("_body") {
+        Some(Value::String(body)) => Some(body),
+        Some(other) => Some(other.to_string()),
+        None => None,
+    };
+    Ok(Event { headers, body })
+}
+
+fn parse_xml_event(src: &str) -> Result<Event, EslError> {
+    let mut reader = quick_xml::Reader::from_str(src);
+    let mut headers = HashMap::new();
+    let mut body = None;
+    let mut in_headers = false;
+    let mut in_body = false;
+    let mut current_header: Option<String> = None;
+    let mut current_value = String::new();
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| EslError::ProtocolError(e.to_string()))?;
+        match event {
+            XmlEvent::Start(tag) => {
+                let name = String::from_utf8_lossy(tag.local_name().as_ref()).to_string();
+                match name.as_str() {
+                    "headers" if !in_headers => in_headers = true,
+                    "body" if !in_headers => {
+                        in_body = true;
+                        body = Some(String::new());
+                    }
+                    _ if in_headers => {
+                        current_header = Some(name);
+                        current_value.clear();
+                    }
+                    _ => {}
+                }
+            }
+            XmlEvent::Empty(tag) if in_headers => {
+                let name = String::from_utf8_lossy(tag.local_name().as_ref()).to_string();
+                headers.insert(name, Value::from(String::new()));
+            }
+            XmlEvent::Text(text) => {
+                let text = text
+                    .unescape()
+                    .map_err(|e| EslError::ProtocolError(e.to_string()))?;
+                if current_header.is_some() {
+                    current_value.push_str(&text);
+                } else if in_body {
+                    if let Some(body) = body.as_mut() {
+                        body.push_str(&text);
+                    }
+                }
+            }
+            XmlEvent::CData(data) => {
+                let text = String::from_utf8_lossy(&data).to_string();
+                if current_header.is_some() {
+                    current_value.push_str(&text);
+                } else if in_body {
+                    if let Some(body) = body.as_mut() {
+                        body.push_str(&text);
+                    }
+                }
+            }
+            XmlEvent::End(tag) => {
+                let name = tag.local_name();
+                let name = name.as_ref();
+                if let Some(header) = current_header.take() {
+                    headers.insert(header, Value::from(url_decode(&current_value)));
+                } else if name == b"headers" {
+                    in_headers = false;
+                } else if name == b"body" {
+                    in_body = false;
+                }
+            }
+            XmlEvent::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(Event { headers, body })
+}
+
 impl Decoder for EslCodec {
     type Item = Event;
     type Error = EslError;
     fn decode(&mut self, src: &mut bytes::BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         trace!("decode");
-        let header_end = get_header_end(src);
-        if header_end.is_none() {
+        let Some(header_end) = get_header_end(src) else {
             return Ok(None);
-        }
-        let header_end = header_end.unwrap();
+        };
         let headers = parse_header(&src[..(header_end - 1)])?;
         trace!("parsed headers are : {:?}", headers);
         let body_start = header_end + 1;
-        let Some(length) = headers.get("Content-Length")else {
+        let Some(length) = headers.get("Content-Length") else {
             src.advance(body_start);
             return Ok(Some(Event {
                 headers,
                 body: None,
-            }))
+            }));
         };
 
-        let length = length.as_str().unwrap();
-        let body_length = length.parse()?;
+        let body_length = length
+            .as_str()
+            .and_then(|length| length.parse().ok())
+            .ok_or_else(|| EslError::ProtocolError(format!("invalid Content-Length {}", length)))?;
         if src.len() < (header_end + body_length + 1) {
             trace!("returned because size was not enough");
             return Ok(None);
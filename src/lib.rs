@@ -64,16 +64,61 @@
 //! }
 //! ```
 
+pub(crate) mod call;
+pub(crate) mod cdr;
+pub(crate) mod channel_data;
+pub(crate) mod channel_tracker;
 pub(crate) mod code;
+pub(crate) mod conference;
 pub(crate) mod connection;
 pub(crate) mod dp_tools;
 pub(crate) mod error;
 pub(crate) mod esl;
 pub(crate) mod event;
+pub(crate) mod event_name;
+pub(crate) mod event_stream;
+pub(crate) mod execute;
+pub(crate) mod filter;
+pub(crate) mod hangup_cause;
 pub(crate) mod io;
+pub(crate) mod log;
+pub(crate) mod originate;
 pub(crate) mod outbound;
+pub(crate) mod outbound_server;
+pub(crate) mod reconnect;
+pub(crate) mod sendevent;
+pub(crate) mod sendmsg;
+pub(crate) mod show;
+pub(crate) mod sofia;
+pub(crate) mod tracker;
 
-pub use connection::EslConnection;
+pub use call::Call;
+pub use cdr::{Cdr, CdrConfig, CdrLeg, CdrRecorder, CdrSink, CsvSink, JsonLinesSink};
+pub use channel_data::ChannelData;
+pub use channel_tracker::{ChannelChange, ChannelTracker, TrackedChannel};
+pub use conference::{
+    parse_conference_list, Conference, ConferenceMember, ConferenceState, ConferenceTracker,
+    MemberTarget,
+};
+pub use connection::{ConnectionState, EslConnection};
 pub use error::*;
 pub use esl::*;
 pub use event::*;
+pub use event_name::*;
+pub use event_stream::{EventPredicate, EventStream, LagPolicy};
+pub use execute::{ExecuteHandle, ExecuteOptions};
+pub use hangup_cause::HangupCause;
+pub use log::{LogForwarder, LogLevel, LogLine};
+pub use originate::{Endpoint, Originate};
+pub use outbound::{Outbound, OutboundConfig};
+pub use outbound_server::{
+    ActiveSession, OutboundServer, OutboundServerHandle, Overflow, ShutdownReport,
+};
+pub use reconnect::ReconnectConfig;
+pub use sendmsg::SendMsg;
+pub use show::{parse_show, CallRow, ChannelRow, ModuleRow, RegistrationRow, ShowRow};
+pub use sofia::{
+    parse_gateway_status, parse_profile_status, parse_sofia_registrations, parse_sofia_status,
+    GatewayState, GatewayStatus, GatewaySummary, GatewayWatcher, ProfileStatus, ProfileSummary,
+    SofiaRegistration, SofiaStatus,
+};
@@ -0,0 +1,239 @@
+use std::fmt;
+
+use futures::{Stream, StreamExt};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::BroadcastStream;
+
+use crate::connection::check_reply;
+use crate::{EslConnection, EslError, Event};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// Level of freeswitch console log, ordered from most to least severe
+pub enum LogLevel {
+    /// `CONSOLE` (0)
+    Console,
+    /// `ALERT` (1)
+    Alert,
+    /// `CRIT` (2)
+    Crit,
+    /// `ERR` (3)
+    Err,
+    /// `WARNING` (4)
+    Warning,
+    /// `NOTICE` (5)
+    Notice,
+    /// `INFO` (6)
+    Info,
+    /// `DEBUG` (7)
+    Debug,
+}
+
+impl LogLevel {
+    /// Returns name as used in `log` command
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Console => "CONSOLE",
+            LogLevel::Alert => "ALERT",
+            LogLevel::Crit => "CRIT",
+            LogLevel::Err => "ERR",
+            LogLevel::Warning => "WARNING",
+            LogLevel::Notice => "NOTICE",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+        }
+    }
+
+    /// Returns number as sent in `Log-Level` header
+    pub fn number(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns level with given number
+    pub fn from_number(number: u8) -> Option<Self> {
+        match number {
+            0 => Some(LogLevel::Console),
+            1 => Some(LogLevel::Alert),
+            2 => Some(LogLevel::Crit),
+            3 => Some(LogLevel::Err),
+            4 => Some(LogLevel::Warning),
+            5 => Some(LogLevel::Notice),
+            6 => Some(LogLevel::Info),
+            7 => Some(LogLevel::Debug),
+            _ => None,
+        }
+    }
+
+    /// Parses level from its name in any case, or from its number
+    pub fn parse(level: &str) -> Option<Self> {
+        if let Ok(number) = level.parse() {
+            return Self::from_number(number);
+        }
+        match level.to_ascii_uppercase().as_str() {
+            "CONSOLE" => Some(LogLevel::Console),
+            "ALERT" => Some(LogLevel::Alert),
+            "CRIT" => Some(LogLevel::Crit),
+            "ERR" | "ERROR" => Some(LogLevel::Err),
+            "WARNING" => Some(LogLevel::Warning),
+            "NOTICE" => Some(LogLevel::Notice),
+            "INFO" => Some(LogLevel::Info),
+            "DEBUG" => Some(LogLevel::Debug),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Line of freeswitch console log received from `log/data` frame
+pub struct LogLine {
+    /// Level at which line was logged
+    pub level: LogLevel,
+    /// Source file which logged line
+    pub file: String,
+    /// Line in source file
+    pub line: u32,
+    /// Function which logged line
+    pub function: String,
+    /// Uuid of channel line was logged for
+    pub channel_uuid: Option<String>,
+    /// Raw `User-Data` header, which carries channel uuid for channel logs
+    pub user_data: Option<String>,
+    /// Line as formatted by freeswitch, without trailing newline
+    pub message: String,
+}
+
+/// Returns true for uuids in their usual `8-4-4-4-12` form
+fn is_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+impl LogLine {
+    /// Builds log line from `log/data` frame
+    pub fn from_frame(frame: &Event) -> Option<Self> {
+        let level = LogLevel::from_number(frame.header("Log-Level")?.parse().ok()?)?;
+        let user_data = frame.header("User-Data").map(ToString::to_string);
+        Some(Self {
+            level,
+            file: frame.header("Log-File").unwrap_or_default().to_string(),
+            line: frame
+                .header("Log-Line")
+                .and_then(|line| line.parse().ok())
+                .unwrap_or_default(),
+            function: frame.header("Log-Func").unwrap_or_default().to_string(),
+            channel_uuid: user_data.clone().filter(|data| is_uuid(data)),
+            user_data,
+            message: frame
+                .body()
+                .as_deref()
+                .unwrap_or_default()
+                .trim_end()
+                .to_string(),
+        })
+    }
+
+    /// emits line as `tracing` event with target `freeswitch`
+    ///
+    /// `CONSOLE` to `ERR` map to error, `NOTICE` and `INFO` to info.
+    pub fn trace(&self) {
+        macro_rules! emit {
+            ($level:ident) => {
+                tracing::$level!(
+                    target: "freeswitch",
+                    file = %self.file,
+                    line = self.line,
+                    function = %self.function,
+                    uuid = self.channel_uuid.as_deref(),
+                    "{}",
+                    self.message
+                )
+            };
+        }
+        match self.level {
+            LogLevel::Console | LogLevel::Alert | LogLevel::Crit | LogLevel::Err => emit!(error),
+            LogLevel::Warning => emit!(warn),
+            LogLevel::Notice | LogLevel::Info => emit!(info),
+            LogLevel::Debug => emit!(debug),
+        }
+    }
+}
+
+impl EslConnection {
+    /// attaches to console log of freeswitch, lines up to given level are
+    /// delivered by [`EslConnection::logs`]
+    ///
+    /// Log level is restored after reconnect.
+    pub async fn log(&self, level: LogLevel) -> Result<Event, EslError> {
+        let command = format!("log {}", level.as_str());
+        let response = check_reply(self.send_recv(command.as_bytes()).await?)?;
+        self.subscriptions.lock().unwrap().log = Some(level);
+        Ok(response)
+    }
+
+    /// detaches from console log of freeswitch
+    pub async fn nolog(&self) -> Result<Event, EslError> {
+        let response = check_reply(self.send_recv(b"nolog").await?)?;
+        self.subscriptions.lock().unwrap().log = None;
+        Ok(response)
+    }
+
+    /// returns stream of raw `log/data` frames received after this call
+    ///
+    /// Log line is in body, its origin in `Log-Level`, `Log-File`, `Log-Line`
+    /// and `Log-Func` headers. Stream ends when connection is closed.
+    pub fn log_frames(&self) -> impl Stream<Item = Event> + Unpin + Send {
+        let receiver = match self.logs.lock().unwrap().as_ref() {
+            Some(sender) => sender.subscribe(),
+            None => broadcast::channel(1).1,
+        };
+        tokio_stream::StreamExt::filter_map(BroadcastStream::new(receiver), Result::ok)
+    }
+
+    /// returns stream of log lines received after this call
+    ///
+    /// Lines missed by lagging stream are skipped, stream ends when connection is closed.
+    pub fn logs(&self) -> impl Stream<Item = LogLine> + Unpin + Send {
+        tokio_stream::StreamExt::filter_map(self.log_frames(), |frame| LogLine::from_frame(&frame))
+    }
+
+    /// attaches to console log at given level and emits every line as
+    /// `tracing` event, see [`LogLine::trace`]
+    pub async fn forward_logs(&self, level: LogLevel) -> Result<LogForwarder, EslError> {
+        let mut logs = self.logs();
+        self.log(level).await?;
+        let task = tokio::spawn(async move {
+            while let Some(line) = logs.next().await {
+                line.trace();
+            }
+        });
+        Ok(LogForwarder { task })
+    }
+}
+
+#[derive(Debug)]
+/// Running forwarding of log lines to `tracing`, stops when dropped
+pub struct LogForwarder {
+    task: JoinHandle<()>,
+}
+
+impl LogForwarder {
+    /// waits until connection is closed
+    pub async fn finished(mut self) {
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for LogForwarder {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
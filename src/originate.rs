@@ -0,0 +1,232 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::{EslConnection, EslError};
+
+/// Escapes value of channel variable used inside `{}` or `[]`
+fn escape(value: &str) -> String {
+    let value = value.replace(',', "\\,");
+    if value.contains(char::is_whitespace) {
+        format!("'{}'", value)
+    } else {
+        value
+    }
+}
+
+fn write_variables(
+    f: &mut fmt::Formatter<'_>,
+    open: char,
+    close: char,
+    variables: &[(String, String)],
+) -> fmt::Result {
+    if variables.is_empty() {
+        return Ok(());
+    }
+    let variables: Vec<_> = variables
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, escape(value)))
+        .collect();
+    write!(f, "{}{}{}", open, variables.join(","), close)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Endpoint dialed by originate, such as `user/1000` or `sofia/gateway/gw/1000`
+pub struct Endpoint {
+    dial_string: String,
+    variables: Vec<(String, String)>,
+}
+
+impl Endpoint {
+    /// Creates endpoint from dial string
+    pub fn new(dial_string: impl ToString) -> Self {
+        Self {
+            dial_string: dial_string.to_string(),
+            variables: Vec::new(),
+        }
+    }
+    /// Sets channel variable for this leg only, sent in `[]`
+    pub fn variable(mut self, name: impl ToString, value: impl ToString) -> Self {
+        self.variables.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_variables(f, '[', ']', &self.variables)?;
+        f.write_str(&self.dial_string)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Destination {
+    Application {
+        name: String,
+        args: String,
+    },
+    Extension {
+        extension: String,
+        dialplan: Option<String>,
+        context: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Builder for `originate` command
+///
+/// Displays as the full command, e.g.
+/// `originate {origination_caller_id_number=1000}user/1001 &park()`.
+pub struct Originate {
+    variables: Vec<(String, String)>,
+    groups: Vec<Vec<Endpoint>>,
+    destination: Destination,
+}
+
+impl Originate {
+    /// Creates originate dialing endpoint, which parks answered channel
+    pub fn new(endpoint: Endpoint) -> Self {
+        Self {
+            variables: Vec::new(),
+            groups: vec![vec![endpoint]],
+            destination: Destination::Application {
+                name: "park".into(),
+                args: String::new(),
+            },
+        }
+    }
+    /// Dials endpoint simultaneously with previous ones, joined with `,`
+    pub fn and(mut self, endpoint: Endpoint) -> Self {
+        if let Some(group) = self.groups.last_mut() {
+            group.push(endpoint);
+        }
+        self
+    }
+    /// Dials endpoint when previous ones fail, joined with `|`
+    pub fn then(mut self, endpoint: Endpoint) -> Self {
+        self.groups.push(vec![endpoint]);
+        self
+    }
+    /// Sets channel variable for every leg, sent in `{}`
+    pub fn variable(mut self, name: impl ToString, value: impl ToString) -> Self {
+        self.variables.push((name.to_string(), value.to_string()));
+        self
+    }
+    /// Sets uuid of new channel using `origination_uuid`
+    pub fn uuid(self, uuid: impl ToString) -> Self {
+        self.variable("origination_uuid", uuid)
+    }
+    /// Sets how long to wait for answer using `originate_timeout`
+    ///
+    /// Freeswitch takes whole seconds and treats 0 as no timeout, so timeout
+    /// is rounded up to at least one second.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        let secs = timeout.as_secs() + u64::from(timeout.subsec_nanos() > 0);
+        self.variable("originate_timeout", secs.max(1))
+    }
+    /// Sets caller id number presented to endpoint
+    pub fn caller_id_number(self, number: impl ToString) -> Self {
+        self.variable("origination_caller_id_number", number)
+    }
+    /// Sets caller id name presented to endpoint
+    pub fn caller_id_name(self, name: impl ToString) -> Self {
+        self.variable("origination_caller_id_name", name)
+    }
+    /// Runs application on answered channel, sent as `&name(args)`
+    pub fn application(mut self, name: impl ToString, args: impl ToString) -> Self {
+        self.destination = Destination::Application {
+            name: name.to_string(),
+            args: args.to_string(),
+        };
+        self
+    }
+    /// Transfers answered channel to extension in dialplan
+    pub fn extension(mut self, extension: impl ToString) -> Self {
+        self.destination = Destination::Extension {
+            extension: extension.to_string(),
+            dialplan: None,
+            context: None,
+        };
+        self
+    }
+    /// Sets dialplan used with [`Originate::extension`], defaults to `XML`
+    pub fn dialplan(mut self, name: impl ToString) -> Self {
+        if let Destination::Extension {
+            ref mut dialplan, ..
+        } = self.destination
+        {
+            *dialplan = Some(name.to_string());
+        }
+        self
+    }
+    /// Sets context used with [`Originate::extension`], defaults to `default`
+    pub fn context(mut self, name: impl ToString) -> Self {
+        if let Destination::Extension {
+            ref mut context, ..
+        } = self.destination
+        {
+            *context = Some(name.to_string());
+        }
+        self
+    }
+}
+
+impl fmt::Display for Originate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("originate ")?;
+        write_variables(f, '{', '}', &self.variables)?;
+        let groups: Vec<_> = self
+            .groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect();
+        write!(f, "{} ", groups.join("|"))?;
+        match self.destination {
+            Destination::Application { ref name, ref args } => {
+                let application = format!("&{}({})", name, args);
+                if application.contains(char::is_whitespace) {
+                    write!(f, "'{}'", application)
+                } else {
+                    f.write_str(&application)
+                }
+            }
+            Destination::Extension {
+                ref extension,
+                ref dialplan,
+                ref context,
+            } => {
+                f.write_str(extension)?;
+                match (dialplan, context) {
+                    (Some(dialplan), Some(context)) => write!(f, " {} {}", dialplan, context),
+                    (Some(dialplan), None) => write!(f, " {}", dialplan),
+                    (None, Some(context)) => write!(f, " XML {}", context),
+                    (None, None) => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl EslConnection {
+    /// originates call using `api`, returning uuid of new channel
+    ///
+    /// Fails with [`EslError::CallFailed`] when call is not answered.
+    /// Waits until call is answered or fails, see [`EslConnection::bgoriginate`].
+    pub async fn originate(&self, originate: &Originate) -> Result<String, EslError> {
+        self.api(&originate.to_string())
+            .await
+            .map(|uuid| uuid.trim().to_string())
+    }
+
+    /// originates call using `bgapi`, returning uuid of new channel
+    pub async fn bgoriginate(&self, originate: &Originate) -> Result<String, EslError> {
+        self.bgapi(&originate.to_string())
+            .await
+            .map(|uuid| uuid.trim().to_string())
+    }
+}
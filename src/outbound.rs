@@ -2,20 +2,101 @@ use std::net::SocketAddr;
 
 use tokio::net::{TcpListener, ToSocketAddrs};
 
-use crate::{connection::EslConnection, EslConnectionType, EslError};
+use crate::{connection::EslConnection, EslConnectionType, EslError, Event};
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// Mode in which freeswitch runs `socket` application connecting to outbound server
+///
+/// Has to match arguments of dialplan application, such as `socket 127.0.0.1:8085 async full`.
+pub struct OutboundConfig {
+    pub(crate) async_mode: bool,
+    pub(crate) full: bool,
+}
+
+impl OutboundConfig {
+    /// Creates config for `socket` application without arguments, which blocks
+    /// dialplan until socket is closed
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Sets `async`, dialplan does not wait for commands sent over socket
+    ///
+    /// Executes get `event-lock` by default so that concurrently issued applications
+    /// run in order in which they were sent, each still waits for its own completion.
+    /// It can be turned off per application with [`ExecuteOptions::without_event_lock`](crate::ExecuteOptions::without_event_lock).
+    pub fn async_mode(mut self) -> Self {
+        self.async_mode = true;
+        self
+    }
+    /// Sets `full`, session gets access to every api command and event like inbound connection
+    pub fn full(mut self) -> Self {
+        self.full = true;
+        self
+    }
+    /// Returns true when `async` is set
+    pub fn is_async(&self) -> bool {
+        self.async_mode
+    }
+    /// Returns true when `full` is set
+    pub fn is_full(&self) -> bool {
+        self.full
+    }
+
+    /// Checks `Socket-Mode` and `Control` headers of `connect` reply against config
+    ///
+    /// Mismatch means dialplan and server disagree, headers missing from
+    /// older freeswitch versions are not checked.
+    pub(crate) fn check_connect_reply(&self, reply: &Event) -> Result<(), EslError> {
+        let expected = [
+            (
+                "Socket-Mode",
+                if self.async_mode { "async" } else { "static" },
+            ),
+            ("Control", if self.full { "full" } else { "single-channel" }),
+        ];
+        for (header, expected) in expected {
+            match reply.header(header) {
+                Some(actual) if actual != expected => {
+                    return Err(EslError::ProtocolError(format!(
+                        "socket application runs with {} {} but server expects {}",
+                        header, actual, expected
+                    )));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+/// Server accepting sessions from freeswitch `socket` application
 pub struct Outbound {
-    listener: TcpListener,
+    pub(crate) listener: TcpListener,
+    pub(crate) config: OutboundConfig,
 }
 impl Outbound {
-    pub(crate) async fn bind(addr: impl ToSocketAddrs) -> Result<Self, EslError> {
+    pub(crate) async fn bind(
+        addr: impl ToSocketAddrs,
+        config: OutboundConfig,
+    ) -> Result<Self, EslError> {
         let listener = TcpListener::bind(addr).await?;
-        Ok(Self { listener })
+        Ok(Self { listener, config })
     }
+    /// Accepts session and completes `connect` handshake before returning it
     pub async fn accept(&self) -> Result<(EslConnection, SocketAddr), EslError> {
         let (stream, addr) = self.listener.accept().await?;
         let connection =
-            EslConnection::with_tcpstream(stream, "None", EslConnectionType::Outbound, None).await?;
+            EslConnection::with_tcpstream(stream, "None", EslConnectionType::Outbound(self.config))
+                .await?;
         Ok((connection, addr))
     }
+    /// Returns local address server is listening on
+    pub fn local_addr(&self) -> Result<SocketAddr, EslError> {
+        Ok(self.listener.local_addr()?)
+    }
+    /// Returns mode of sessions accepted by server
+    pub fn config(&self) -> OutboundConfig {
+        self.config
+    }
 }
@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::{oneshot, watch, OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinHandle;
+use tracing::{error, trace, warn};
+
+use crate::{EslConnection, EslConnectionType, EslError, Outbound};
+
+/// Time given to freeswitch to acknowledge `exit` of session cut by shutdown
+const EXIT_TIMEOUT: Duration = Duration::from_secs(1);
+/// Delay after first failed accept, doubled on every failure in a row
+const ACCEPT_INITIAL_DELAY: Duration = Duration::from_millis(10);
+/// Upper bound of delay between failed accepts
+const ACCEPT_MAX_DELAY: Duration = Duration::from_secs(1);
+
+/// Returns false for errors after which listener can not accept any session
+///
+/// Errors such as running out of file descriptors go away once sessions finish.
+fn is_transient(error: &io::Error) -> bool {
+    !matches!(
+        error.kind(),
+        io::ErrorKind::InvalidInput | io::ErrorKind::NotConnected | io::ErrorKind::Unsupported
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// What happens with sessions arriving while maximum number of sessions is active
+pub enum Overflow {
+    /// Leave sessions in listen backlog until running session finishes
+    #[default]
+    Queue,
+    /// Close socket right away, freeswitch continues dialplan after `socket` application
+    Reject,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Session handled by [`OutboundServer`]
+pub struct ActiveSession {
+    /// Id of session, unique within server
+    pub id: u64,
+    /// Address freeswitch connected from
+    pub addr: SocketAddr,
+    /// Uuid of channel, known once `connect` handshake completes
+    pub uuid: Option<String>,
+    /// Time at which session was accepted
+    pub started: SystemTime,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Outcome of [`OutboundServerHandle::shutdown`]
+pub struct ShutdownReport {
+    /// Sessions which finished before deadline
+    pub drained: Vec<ActiveSession>,
+    /// Sessions still running at deadline, which were disconnected
+    pub cut: Vec<ActiveSession>,
+}
+
+#[derive(Debug)]
+struct Session {
+    info: ActiveSession,
+    connection: Option<EslConnection>,
+    task: Option<JoinHandle<()>>,
+}
+
+type Sessions = watch::Sender<HashMap<u64, Session>>;
+
+/// Removes session from server once its task finishes or is aborted
+struct SessionGuard {
+    id: u64,
+    sessions: Arc<Sessions>,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        self.sessions.send_modify(|sessions| {
+            sessions.remove(&self.id);
+        });
+    }
+}
+
+#[derive(Debug)]
+/// Server running handler for every session of freeswitch `socket` application
+///
+/// Unlike looping over [`Outbound::accept`], number of concurrent sessions can be
+/// limited and server can be shut down without cutting calls in progress.
+pub struct OutboundServer {
+    outbound: Outbound,
+    max_sessions: Option<usize>,
+    overflow: Overflow,
+}
+
+impl OutboundServer {
+    /// Creates server accepting sessions from given listener, without session limit
+    pub fn new(outbound: Outbound) -> Self {
+        Self {
+            outbound,
+            max_sessions: None,
+            overflow: Overflow::default(),
+        }
+    }
+    /// Limits number of sessions handled at the same time
+    pub fn max_sessions(mut self, max_sessions: usize) -> Self {
+        self.max_sessions = Some(max_sessions);
+        self
+    }
+    /// Sets what happens with sessions above limit, queued by default
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// starts accepting sessions, every session is passed to handler in its own task
+    ///
+    /// Errors returned by handler are logged.
+    pub fn serve<F, Fut>(self, handler: F) -> OutboundServerHandle
+    where
+        F: Fn(EslConnection) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), EslError>> + Send + 'static,
+    {
+        let local_addr = self.outbound.local_addr().ok();
+        let (sessions, _) = watch::channel(HashMap::new());
+        let sessions = Arc::new(sessions);
+        let (stop_tx, stop_rx) = oneshot::channel();
+        let accept =
+            tokio::spawn(self.accept_loop(Arc::new(handler), Arc::clone(&sessions), stop_rx));
+        OutboundServerHandle {
+            local_addr,
+            sessions,
+            stop: Some(stop_tx),
+            accept,
+        }
+    }
+
+    async fn accept_loop<F, Fut>(
+        self,
+        handler: Arc<F>,
+        sessions: Arc<Sessions>,
+        mut stop: oneshot::Receiver<()>,
+    ) where
+        F: Fn(EslConnection) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), EslError>> + Send + 'static,
+    {
+        let limit = self.max_sessions.map(|max| Arc::new(Semaphore::new(max)));
+        let mut next_id = 0;
+        let mut accept_delay = ACCEPT_INITIAL_DELAY;
+        loop {
+            let queued_permit = match (&limit, self.overflow) {
+                (Some(limit), Overflow::Queue) => tokio::select! {
+                    _ = &mut stop => return,
+                    permit = Arc::clone(limit).acquire_owned() => permit.ok(),
+                },
+                _ => None,
+            };
+            let (stream, addr) = tokio::select! {
+                _ = &mut stop => return,
+                accepted = self.outbound.listener.accept() => match accepted {
+                    Ok(accepted) => accepted,
+                    Err(e) if is_transient(&e) => {
+                        warn!(
+                            "unable to accept outbound session, retrying in {:?}: {}",
+                            accept_delay, e
+                        );
+                        tokio::select! {
+                            _ = &mut stop => return,
+                            _ = tokio::time::sleep(accept_delay) => {}
+                        }
+                        accept_delay = (accept_delay * 2).min(ACCEPT_MAX_DELAY);
+                        continue;
+                    }
+                    Err(e) => {
+                        error!("outbound server stopped accepting sessions: {}", e);
+                        return;
+                    }
+                },
+            };
+            accept_delay = ACCEPT_INITIAL_DELAY;
+            let permit: Option<OwnedSemaphorePermit> = match (&limit, queued_permit) {
+                (_, Some(permit)) => Some(permit),
+                (Some(limit), None) => match Arc::clone(limit).try_acquire_owned() {
+                    Ok(permit) => Some(permit),
+                    Err(_) => {
+                        warn!("rejecting outbound session from {}, limit reached", addr);
+                        continue;
+                    }
+                },
+                (None, None) => None,
+            };
+            let id = next_id;
+            next_id += 1;
+            // session is registered before its task runs, so that the task
+            // always finds it when removing itself
+            sessions.send_modify(|sessions| {
+                let info = ActiveSession {
+                    id,
+                    addr,
+                    uuid: None,
+                    started: SystemTime::now(),
+                };
+                sessions.insert(
+                    id,
+                    Session {
+                        info,
+                        connection: None,
+                        task: None,
+                    },
+                );
+            });
+            let guard = SessionGuard {
+                id,
+                sessions: Arc::clone(&sessions),
+            };
+            let handler = Arc::clone(&handler);
+            let config = self.outbound.config;
+            let task = tokio::spawn(async move {
+                let _permit = permit;
+                let connection = match EslConnection::with_tcpstream(
+                    stream,
+                    "None",
+                    EslConnectionType::Outbound(config),
+                )
+                .await
+                {
+                    Ok(connection) => connection,
+                    Err(e) => {
+                        warn!("outbound session from {} failed to connect: {}", addr, e);
+                        return;
+                    }
+                };
+                guard.sessions.send_modify(|sessions| {
+                    if let Some(session) = sessions.get_mut(&guard.id) {
+                        session.info.uuid = connection.call_uuid.clone();
+                        session.connection = Some(connection.handle());
+                    }
+                });
+                if let Err(e) = handler(connection).await {
+                    warn!("outbound session {} failed: {}", guard.id, e);
+                }
+                trace!("outbound session {} finished", guard.id);
+            });
+            sessions.send_modify(|sessions| {
+                if let Some(session) = sessions.get_mut(&id) {
+                    session.task = Some(task);
+                }
+            });
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Running [`OutboundServer`], stops accepting sessions when dropped
+pub struct OutboundServerHandle {
+    local_addr: Option<SocketAddr>,
+    sessions: Arc<Sessions>,
+    stop: Option<oneshot::Sender<()>>,
+    accept: JoinHandle<()>,
+}
+
+impl OutboundServerHandle {
+    /// returns local address server is listening on
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// returns sessions being handled, ordered by id
+    pub fn active_sessions(&self) -> Vec<ActiveSession> {
+        let mut sessions: Vec<_> = self
+            .sessions
+            .borrow()
+            .values()
+            .map(|session| session.info.clone())
+            .collect();
+        sessions.sort_by_key(|session| session.id);
+        sessions
+    }
+
+    /// stops accepting sessions and waits until active ones finish
+    ///
+    /// Sessions still running after deadline are disconnected with `exit`
+    /// and reported as cut, their handlers are stopped before this returns.
+    pub async fn shutdown(mut self, deadline: Duration) -> ShutdownReport {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        let _ = (&mut self.accept).await;
+        let running = self.active_sessions();
+        let mut finished = self.sessions.subscribe();
+        let _ = tokio::time::timeout(deadline, finished.wait_for(HashMap::is_empty)).await;
+        drop(finished);
+
+        let mut remaining = Vec::new();
+        self.sessions.send_modify(|sessions| {
+            for session in sessions.values_mut() {
+                remaining.push((
+                    session.info.clone(),
+                    session.connection.take(),
+                    session.task.take(),
+                ));
+            }
+        });
+        let mut cut = Vec::new();
+        for (info, connection, task) in remaining {
+            if let Some(task) = &task {
+                task.abort();
+            }
+            if let Some(connection) = connection {
+                if tokio::time::timeout(EXIT_TIMEOUT, connection.disconnect())
+                    .await
+                    .is_err()
+                {
+                    warn!("outbound session {} did not acknowledge exit", info.id);
+                }
+            }
+            // once aborted task is joined, none of its code runs any more
+            if let Some(task) = task {
+                let _ = task.await;
+            }
+            cut.push(info);
+        }
+        cut.sort_by_key(|session| session.id);
+        let drained = running
+            .into_iter()
+            .filter(|session| !cut.iter().any(|cut| cut.id == session.id))
+            .collect();
+        ShutdownReport { drained, cut }
+    }
+}
+
+impl Drop for OutboundServerHandle {
+    fn drop(&mut self) {
+        self.accept.abort();
+    }
+}
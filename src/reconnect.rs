@@ -0,0 +1,206 @@
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use tokio::net::{TcpStream, ToSocketAddrs};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt;
+use tracing::{trace, warn};
+
+use crate::connection::{check_reply, ConnectionState, EslConnection, TransportRx};
+use crate::EslError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Backoff settings used by reconnecting inbound connection
+pub struct ReconnectConfig {
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: u32,
+    max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2,
+            max_attempts: None,
+        }
+    }
+}
+
+impl ReconnectConfig {
+    /// Sets delay before first reconnect attempt, defaults to 500ms
+    pub fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+    /// Sets upper bound for delay between attempts, defaults to 30s
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+    /// Sets factor by which delay grows after every failed attempt, defaults to 2
+    pub fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+    /// Sets number of attempts after which connection is given up, defaults to unlimited
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+    fn next_delay(&self, delay: Duration) -> Duration {
+        delay.saturating_mul(self.multiplier).min(self.max_delay)
+    }
+}
+
+async fn wait_for_auth_request(transport_rx: &mut TransportRx) -> Result<(), EslError> {
+    match transport_rx.next().await {
+        Some(Ok(event)) if event.header("Content-Type") == Some("auth/request") => Ok(()),
+        Some(Ok(event)) => Err(EslError::InternalError(format!(
+            "expected auth/request but got {:?}",
+            event.header("Content-Type")
+        ))),
+        Some(Err(e)) => Err(e),
+        None => Err(EslError::ConnectionLost),
+    }
+}
+
+impl EslConnection {
+    pub(crate) async fn reconnecting<A>(
+        addr: A,
+        password: impl ToString,
+        config: ReconnectConfig,
+    ) -> Result<Self, EslError>
+    where
+        A: ToSocketAddrs + Clone + Send + Sync + 'static,
+    {
+        let stream = TcpStream::connect(addr.clone()).await?;
+        let (mut transport_rx, transport_tx) = Self::split_transport(stream);
+        wait_for_auth_request(&mut transport_rx).await?;
+        let mut connection = Self::from_transport(transport_tx, password);
+        let reader = connection.start_reader(transport_rx);
+        let handshake = async {
+            connection.auth().await?;
+            connection
+                .subscribe(vec!["BACKGROUND_JOB", "CHANNEL_EXECUTE_COMPLETE"])
+                .await
+        };
+        if let Err(e) = handshake.await {
+            reader.abort();
+            return Err(e);
+        }
+        connection.connected.store(true, Ordering::Relaxed);
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        connection.shutdown = Some(shutdown_tx);
+        let supervisor = connection.handle();
+        tokio::spawn(async move {
+            supervisor
+                .supervise(addr, config, reader, shutdown_rx)
+                .await
+        });
+        Ok(connection)
+    }
+
+    fn start_reader(&self, transport_rx: TransportRx) -> JoinHandle<EslError> {
+        let reader = self.handle();
+        tokio::spawn(async move { reader.read_events(transport_rx).await })
+    }
+
+    /// authenticates again and restores every subscription and filter of connection
+    ///
+    /// Attempt fails when freeswitch rejects any of them, so that it is retried.
+    async fn restore(&self) -> Result<(), EslError> {
+        self.auth().await?;
+        let commands = self.subscriptions.lock().unwrap().restore_commands();
+        for command in commands {
+            check_reply(self.request(command.as_bytes()).await?)?;
+        }
+        Ok(())
+    }
+
+    async fn reconnect<A: ToSocketAddrs>(&self, addr: A) -> Result<JoinHandle<EslError>, EslError> {
+        let stream = TcpStream::connect(addr).await?;
+        let (mut transport_rx, transport_tx) = Self::split_transport(stream);
+        wait_for_auth_request(&mut transport_rx).await?;
+        *self.transport_tx.lock().await = transport_tx;
+        let mut reader = self.start_reader(transport_rx);
+        let result = tokio::select! {
+            result = self.restore() => result,
+            _ = &mut reader => Err(EslError::ConnectionLost),
+        };
+        match result {
+            Ok(()) => Ok(reader),
+            Err(e) => {
+                reader.abort();
+                Err(e)
+            }
+        }
+    }
+
+    async fn supervise<A: ToSocketAddrs + Clone>(
+        &self,
+        addr: A,
+        config: ReconnectConfig,
+        mut reader: JoinHandle<EslError>,
+        mut shutdown_rx: oneshot::Receiver<()>,
+    ) {
+        loop {
+            let result = tokio::select! {
+                result = &mut reader => result,
+                _ = &mut shutdown_rx => {
+                    reader.abort();
+                    // handles such as calls or conferences may outlive primary connection
+                    self.connection_lost(ConnectionState::Disconnected, EslError::Disconnected)
+                        .await;
+                    return;
+                }
+            };
+            let mut error = match result {
+                Ok(EslError::Disconnected) | Err(_) => EslError::ConnectionLost,
+                Ok(e) => e,
+            };
+            if self.connection_state() == ConnectionState::Disconnected {
+                self.connection_lost(ConnectionState::Disconnected, error)
+                    .await;
+                return;
+            }
+            warn!("connection to freeswitch lost, reconnecting");
+            let mut delay = config.initial_delay;
+            let mut attempt = 1;
+            reader = loop {
+                if config.max_attempts.is_some_and(|max| attempt > max) {
+                    warn!("giving up reconnecting after {} attempts", attempt - 1);
+                    self.connection_lost(ConnectionState::Disconnected, error)
+                        .await;
+                    return;
+                }
+                // also fails replies left over from previous failed attempt
+                self.connection_lost(ConnectionState::Reconnecting { attempt }, error)
+                    .await;
+                error = EslError::ConnectionLost;
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = &mut shutdown_rx => {
+                        self.connection_lost(ConnectionState::Disconnected, EslError::Disconnected)
+                            .await;
+                        return;
+                    }
+                }
+                match self.reconnect(addr.clone()).await {
+                    Ok(reader) => break reader,
+                    Err(e) => {
+                        trace!("reconnect attempt {} failed: {}", attempt, e);
+                        delay = config.next_delay(delay);
+                        attempt += 1;
+                    }
+                }
+            };
+            // published only once subscriptions are restored
+            self.connected.store(true, Ordering::Relaxed);
+            self.state.send_replace(ConnectionState::Connected);
+        }
+    }
+}
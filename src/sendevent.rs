@@ -0,0 +1,47 @@
+use serde_json::Value;
+
+use crate::connection::check_reply;
+use crate::io::push_header;
+use crate::{EslConnection, EslError, Event};
+
+/// Serializes `sendevent` command with headers of event, sorted by name
+///
+/// Body is not included, it is framed with `Content-Length` by codec.
+fn sendevent_command(event: &Event) -> Result<String, EslError> {
+    let name = event
+        .header("Event-Name")
+        .ok_or_else(|| EslError::InvalidEvent("missing Event-Name header".into()))?;
+    let mut headers: Vec<_> = event
+        .headers()
+        .iter()
+        .filter(|(name, _)| !name.eq_ignore_ascii_case("Content-Length"))
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            (name.as_str(), value)
+        })
+        .collect();
+    headers.sort();
+    let mut command = format!("sendevent {}", name);
+    for (name, value) in headers {
+        push_header(&mut command, name, &value)?;
+    }
+    Ok(command)
+}
+
+impl EslConnection {
+    /// fires event into freeswitch, such as `NOTIFY`, `SEND_MESSAGE` or `CUSTOM` one
+    ///
+    /// Events are usually built with [`EventBuilder`](crate::EventBuilder), body is
+    /// sent with its `Content-Length`.
+    pub async fn sendevent(&self, event: &Event) -> Result<Event, EslError> {
+        let command = sendevent_command(event)?;
+        let body = event.body().as_deref().map(str::as_bytes);
+        let response = self
+            .send_frame(command.as_bytes(), body, true, self.default_timeout())
+            .await?;
+        check_reply(response)
+    }
+}
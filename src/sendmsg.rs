@@ -0,0 +1,133 @@
+use std::net::SocketAddr;
+
+use crate::connection::check_reply;
+use crate::io::push_header;
+use crate::{EslConnection, EslError, Event, HangupCause};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Message controlling channel, sent with `sendmsg`
+///
+/// Created for one of call commands, messages go to channel of connection
+/// unless other one is selected with [`SendMsg::uuid`].
+pub struct SendMsg {
+    uuid: Option<String>,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+}
+
+impl SendMsg {
+    /// Creates message with given `call-command`
+    pub fn new(call_command: &str) -> Self {
+        Self {
+            uuid: None,
+            headers: vec![("call-command".into(), call_command.into())],
+            body: None,
+        }
+    }
+    /// Executes dialplan application, without waiting for it to complete
+    ///
+    /// Use [`EslConnection::execute`] to wait for result of application.
+    pub fn execute(app_name: &str, app_args: &str) -> Self {
+        Self::new("execute")
+            .header("execute-app-name", app_name)
+            .header("execute-app-arg", app_args)
+    }
+    /// Hangs up channel with given cause
+    pub fn hangup(cause: HangupCause) -> Self {
+        Self::new("hangup").header("hangup-cause", cause)
+    }
+    /// Takes channel with given uuid out of media path, bridged legs exchange media directly
+    pub fn nomedia(uuid: &str) -> Self {
+        Self::new("nomedia").header("nomedia-uuid", uuid)
+    }
+    /// Streams media of channel between given local and remote address
+    pub fn unicast(local: SocketAddr, remote: SocketAddr) -> Self {
+        Self::new("unicast")
+            .header("local-ip", local.ip())
+            .header("local-port", local.port())
+            .header("remote-ip", remote.ip())
+            .header("remote-port", remote.port())
+    }
+    /// Transfers channel to inline extension, built with [`SendMsg::application`]
+    pub fn xferext() -> Self {
+        Self::new("xferext")
+    }
+    /// Sets `transport` of `unicast`, `udp` or `tcp`
+    pub fn transport(self, transport: &str) -> Self {
+        self.header("transport", transport)
+    }
+    /// Sets `flags` of `unicast`, such as `native`
+    pub fn flags(self, flags: &str) -> Self {
+        self.header("flags", flags)
+    }
+    /// Appends application to extension of `xferext`, applications run in order
+    pub fn application(self, app_name: &str, app_args: &str) -> Self {
+        if app_args.is_empty() {
+            self.header("application", app_name)
+        } else {
+            self.header("application", format!("{} {}", app_name, app_args))
+        }
+    }
+    /// Sends message to channel with given uuid instead of channel of connection
+    pub fn uuid(mut self, uuid: impl ToString) -> Self {
+        self.uuid = Some(uuid.to_string());
+        self
+    }
+    /// Appends header, repeated headers are sent in order
+    pub fn header(mut self, name: impl ToString, value: impl ToString) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+    /// Sets body with its `content-type`, body is sent with its `Content-Length`
+    pub fn body(mut self, content_type: &str, body: impl ToString) -> Self {
+        self.headers
+            .retain(|(name, _)| !name.eq_ignore_ascii_case("content-type"));
+        self.body = Some(body.to_string());
+        self.header("content-type", content_type)
+    }
+
+    /// Serializes `sendmsg` command for given channel, body is framed by codec
+    pub(crate) fn command(&self, call_uuid: Option<&str>) -> Result<String, EslError> {
+        let uuid = self
+            .uuid
+            .as_deref()
+            .or(call_uuid)
+            .ok_or(EslError::NoCallUuid)?;
+        let mut command = format!("sendmsg {}", uuid);
+        for (name, value) in &self.headers {
+            push_header(&mut command, name, value)?;
+        }
+        Ok(command)
+    }
+
+    pub(crate) fn has_header(&self, name: &str) -> bool {
+        self.header_value(name).is_some()
+    }
+
+    pub(crate) fn header_value(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn body_bytes(&self) -> Option<&[u8]> {
+        self.body.as_deref().map(str::as_bytes)
+    }
+}
+
+impl EslConnection {
+    /// sends message to channel and returns reply once freeswitch accepted it
+    pub async fn sendmsg(&self, message: &SendMsg) -> Result<Event, EslError> {
+        let command = message.command(self.call_uuid.as_deref())?;
+        let response = self
+            .send_frame(
+                command.as_bytes(),
+                message.body_bytes(),
+                true,
+                self.default_timeout(),
+            )
+            .await?;
+        check_reply(response)
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::{EslConnection, EslError};
+
+/// Row of tabular `show` output
+pub trait ShowRow: Sized {
+    /// Argument of `show` command listing these rows
+    const COMMAND: &'static str;
+    /// Creates row from column names and values, missing columns are left empty
+    fn from_columns(columns: &HashMap<String, String>) -> Self;
+}
+
+macro_rules! column_name {
+    ($field:ident) => {
+        stringify!($field)
+    };
+    ($field:ident $column:literal) => {
+        $column
+    };
+}
+
+macro_rules! show_rows {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident => $command:literal {
+            $($field:ident $(as $column:literal)?,)*
+        }
+    )*) => {
+        $(
+            #[derive(Debug, Clone, Default, PartialEq, Eq)]
+            $(#[$meta])*
+            pub struct $name {
+                $(
+                    #[doc = concat!("`", column_name!($field $($column)?), "` column")]
+                    pub $field: String,
+                )*
+            }
+
+            impl ShowRow for $name {
+                const COMMAND: &'static str = $command;
+                fn from_columns(columns: &HashMap<String, String>) -> Self {
+                    Self {
+                        $(
+                            $field: columns
+                                .get(column_name!($field $($column)?))
+                                .cloned()
+                                .unwrap_or_default(),
+                        )*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+show_rows! {
+    /// Row of `show channels`
+    ChannelRow => "channels" {
+        uuid,
+        direction,
+        created,
+        created_epoch,
+        name,
+        state,
+        cid_name,
+        cid_num,
+        ip_addr,
+        dest,
+        application,
+        application_data,
+        dialplan,
+        context,
+        read_codec,
+        read_rate,
+        read_bit_rate,
+        write_codec,
+        write_rate,
+        write_bit_rate,
+        secure,
+        hostname,
+        presence_id,
+        presence_data,
+        accountcode,
+        callstate,
+        callee_name,
+        callee_num,
+        callee_direction,
+        call_uuid,
+        sent_callee_name,
+        sent_callee_num,
+        initial_cid_name,
+        initial_cid_num,
+        initial_ip_addr,
+        initial_dest,
+        initial_dialplan,
+        initial_context,
+    }
+
+    /// Row of `show calls`, bridged channels with their b-leg
+    CallRow => "calls" {
+        uuid,
+        direction,
+        created,
+        created_epoch,
+        name,
+        state,
+        cid_name,
+        cid_num,
+        ip_addr,
+        dest,
+        presence_id,
+        presence_data,
+        accountcode,
+        callstate,
+        callee_name,
+        callee_num,
+        callee_direction,
+        call_uuid,
+        hostname,
+        sent_callee_name,
+        sent_callee_num,
+        b_uuid,
+        b_direction,
+        b_created,
+        b_created_epoch,
+        b_name,
+        b_state,
+        b_cid_name,
+        b_cid_num,
+        b_ip_addr,
+        b_dest,
+        b_presence_id,
+        b_presence_data,
+        b_accountcode,
+        b_callstate,
+        b_callee_name,
+        b_callee_num,
+        b_callee_direction,
+        b_sent_callee_name,
+        b_sent_callee_num,
+        call_created_epoch,
+    }
+
+    /// Row of `show registrations`
+    RegistrationRow => "registrations" {
+        reg_user,
+        realm,
+        token,
+        url,
+        expires,
+        network_ip,
+        network_port,
+        network_proto,
+        hostname,
+        metadata,
+    }
+
+    /// Row of `show modules`
+    ModuleRow => "modules" {
+        module_type as "type",
+        name,
+        ikey,
+        filename,
+    }
+}
+
+fn parse_json_rows(output: &str) -> Result<Vec<HashMap<String, String>>, EslError> {
+    let output: Value = serde_json::from_str(output)
+        .map_err(|e| EslError::ProtocolError(format!("invalid show output: {}", e)))?;
+    let Some(rows) = output.get("rows") else {
+        // `{"row_count":0}` has no rows
+        return Ok(Vec::new());
+    };
+    let rows = rows
+        .as_array()
+        .ok_or_else(|| EslError::ProtocolError("rows of show output is not array".into()))?;
+    rows.iter()
+        .map(|row| {
+            let row = row.as_object().ok_or_else(|| {
+                EslError::ProtocolError("row of show output is not object".into())
+            })?;
+            Ok(row
+                .iter()
+                .map(|(column, value)| {
+                    let value = match value {
+                        Value::String(value) => value.clone(),
+                        Value::Null => String::new(),
+                        value => value.to_string(),
+                    };
+                    (column.clone(), value)
+                })
+                .collect())
+        })
+        .collect()
+}
+
+fn is_total(line: &str) -> bool {
+    line.strip_suffix(" total.")
+        .is_some_and(|count| count.parse::<u64>().is_ok())
+}
+
+fn parse_delimited_rows(output: &str) -> Result<Vec<HashMap<String, String>>, EslError> {
+    let mut lines = output
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .take_while(|line| !is_total(line));
+    let Some(header) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let columns: Vec<_> = header.split(',').collect();
+    lines
+        .map(|line| {
+            let values: Vec<_> = line.split(',').collect();
+            if values.len() != columns.len() {
+                return Err(EslError::ProtocolError(format!(
+                    "expected {} columns in show output but got {}",
+                    columns.len(),
+                    values.len()
+                )));
+            }
+            Ok(columns
+                .iter()
+                .zip(values)
+                .map(|(column, value)| (column.to_string(), value.to_string()))
+                .collect())
+        })
+        .collect()
+}
+
+/// Parses output of `show` command, either `as json` or comma separated
+///
+/// Comma separated output can not be parsed when values contain commas,
+/// prefer `as json` which [`EslConnection::show`] uses.
+pub fn parse_show<T: ShowRow>(output: &str) -> Result<Vec<T>, EslError> {
+    let output = output.trim();
+    let rows = if output.starts_with('{') {
+        parse_json_rows(output)?
+    } else {
+        parse_delimited_rows(output)?
+    };
+    Ok(rows.iter().map(T::from_columns).collect())
+}
+
+impl EslConnection {
+    /// runs `show <rows> as json` and parses its rows
+    pub async fn show<T: ShowRow>(&self) -> Result<Vec<T>, EslError> {
+        let output = self.api(&format!("show {} as json", T::COMMAND)).await?;
+        parse_show(&output)
+    }
+
+    /// returns active channels from `show channels`
+    pub async fn show_channels(&self) -> Result<Vec<ChannelRow>, EslError> {
+        self.show().await
+    }
+
+    /// returns bridged calls from `show calls`
+    pub async fn show_calls(&self) -> Result<Vec<CallRow>, EslError> {
+        self.show().await
+    }
+
+    /// returns registrations from `show registrations`
+    pub async fn show_registrations(&self) -> Result<Vec<RegistrationRow>, EslError> {
+        self.show().await
+    }
+
+    /// returns loaded modules from `show modules`
+    pub async fn show_modules(&self) -> Result<Vec<ModuleRow>, EslError> {
+        self.show().await
+    }
+}
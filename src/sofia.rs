@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use futures::StreamExt;
+use tokio::sync::watch;
+use tracing::warn;
+
+use crate::tracker::Tracked;
+use crate::{EslConnection, EslError, Event, EventPredicate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// Registration state of sofia gateway
+pub enum GatewayState {
+    /// `UNREGED`
+    Unreged,
+    /// `TRYING`
+    Trying,
+    /// `REGISTER`
+    Register,
+    /// `REGED`
+    Reged,
+    /// `UNREGISTER`
+    Unregister,
+    /// `FAILED`
+    Failed,
+    /// `FAIL_WAIT`
+    FailWait,
+    /// `EXPIRED`
+    Expired,
+    /// `NOREG`, gateway does not register
+    Noreg,
+    /// `DOWN`
+    Down,
+    /// `TIMEOUT`
+    Timeout,
+}
+
+impl GatewayState {
+    /// Returns name as printed by `sofia status`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GatewayState::Unreged => "UNREGED",
+            GatewayState::Trying => "TRYING",
+            GatewayState::Register => "REGISTER",
+            GatewayState::Reged => "REGED",
+            GatewayState::Unregister => "UNREGISTER",
+            GatewayState::Failed => "FAILED",
+            GatewayState::FailWait => "FAIL_WAIT",
+            GatewayState::Expired => "EXPIRED",
+            GatewayState::Noreg => "NOREG",
+            GatewayState::Down => "DOWN",
+            GatewayState::Timeout => "TIMEOUT",
+        }
+    }
+
+    /// Parses gateway state
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "UNREGED" => Some(GatewayState::Unreged),
+            "TRYING" => Some(GatewayState::Trying),
+            "REGISTER" => Some(GatewayState::Register),
+            "REGED" => Some(GatewayState::Reged),
+            "UNREGISTER" => Some(GatewayState::Unregister),
+            "FAILED" => Some(GatewayState::Failed),
+            "FAIL_WAIT" => Some(GatewayState::FailWait),
+            "EXPIRED" => Some(GatewayState::Expired),
+            "NOREG" => Some(GatewayState::Noreg),
+            "DOWN" => Some(GatewayState::Down),
+            "TIMEOUT" => Some(GatewayState::Timeout),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GatewayState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Profile listed by `sofia status`
+pub struct ProfileSummary {
+    /// Name of profile or its alias
+    pub name: String,
+    /// Sip url profile listens on
+    pub url: String,
+    /// State of profile, such as `RUNNING`
+    pub state: String,
+    /// Number of calls in progress on profile
+    pub calls: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Gateway listed by `sofia status`
+pub struct GatewaySummary {
+    /// Name of profile gateway belongs to
+    pub profile: String,
+    /// Name of gateway
+    pub name: String,
+    /// Sip url of gateway
+    pub url: String,
+    /// Registration state of gateway
+    pub state: GatewayState,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Parsed output of `sofia status`
+pub struct SofiaStatus {
+    /// Profiles, including those listed under their alias
+    pub profiles: Vec<ProfileSummary>,
+    /// Gateways of every profile
+    pub gateways: Vec<GatewaySummary>,
+    /// Aliases with name of profile they point to
+    pub aliases: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Parsed output of `sofia status profile <name>`
+pub struct ProfileStatus {
+    /// Name of profile
+    pub name: String,
+    /// Sip url profile listens on
+    pub url: String,
+    /// Number of inbound calls handled
+    pub calls_in: u64,
+    /// Number of failed inbound calls
+    pub failed_calls_in: u64,
+    /// Number of outbound calls handled
+    pub calls_out: u64,
+    /// Number of failed outbound calls
+    pub failed_calls_out: u64,
+    /// Every setting printed, keyed by its name such as `RTP-IP`
+    pub settings: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Parsed output of `sofia status gateway <name>`
+pub struct GatewayStatus {
+    /// Name of gateway
+    pub name: String,
+    /// Name of profile gateway belongs to
+    pub profile: String,
+    /// Registration state of gateway
+    pub state: GatewayState,
+    /// Ping status of gateway, `UP` or `DOWN`
+    pub status: String,
+    /// Every setting printed, keyed by its name such as `Proxy`
+    pub settings: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Registration listed by `sofia status profile <name> reg`
+pub struct SofiaRegistration {
+    /// `Call-ID` of register request
+    pub call_id: String,
+    /// Registered user as `user@host`
+    pub user: String,
+    /// Contact of registered device
+    pub contact: String,
+    /// User agent of registered device
+    pub agent: String,
+    /// Registration status including its expiry
+    pub status: String,
+    /// Result of pinging device
+    pub ping_status: String,
+    /// Hostname of freeswitch which accepted registration
+    pub host: String,
+    /// Network address of device
+    pub ip: String,
+    /// Network port of device
+    pub port: String,
+    /// User used for authentication
+    pub auth_user: String,
+    /// Realm used for authentication
+    pub auth_realm: String,
+    /// Account used for message waiting indication
+    pub mwi_account: String,
+}
+
+/// Returns tab separated fields of line with padding removed
+fn fields(line: &str) -> Vec<&str> {
+    line.split('\t').map(str::trim).collect()
+}
+
+fn is_separator(line: &str) -> bool {
+    line.starts_with('=')
+}
+
+/// Parses `key<tab>value` lines into map, erroring with output when there are none
+fn parse_settings(output: &str) -> Result<HashMap<String, String>, EslError> {
+    let settings: HashMap<_, _> = output
+        .lines()
+        .filter(|line| !is_separator(line))
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+    if settings.is_empty() {
+        // freeswitch replies with text such as `Invalid Profile!`
+        return Err(EslError::ApiError(output.trim().to_string()));
+    }
+    Ok(settings)
+}
+
+/// Parses output of `sofia status`
+pub fn parse_sofia_status(output: &str) -> Result<SofiaStatus, EslError> {
+    let mut status = SofiaStatus::default();
+    for line in output.lines().filter(|line| !is_separator(line)) {
+        let [name, kind, data, state] = fields(line)[..] else {
+            continue;
+        };
+        match kind {
+            "profile" => {
+                // state is followed by number of calls and tags such as `(TLS)`
+                let mut words = state.split_whitespace();
+                let state = words.next().unwrap_or_default();
+                let calls = match words.next() {
+                    Some(calls) => calls.trim_matches(|c| c == '(' || c == ')').parse().ok(),
+                    None => Some(0),
+                };
+                let Some(calls) = calls else {
+                    warn!("skipping unparsable sofia profile line {:?}", line);
+                    continue;
+                };
+                status.profiles.push(ProfileSummary {
+                    name: name.to_string(),
+                    url: data.to_string(),
+                    state: state.to_string(),
+                    calls,
+                });
+            }
+            "gateway" => {
+                let (profile, gateway) = name.split_once("::").unwrap_or(("", name));
+                let state = state.split_whitespace().next().unwrap_or_default();
+                let Some(state) = GatewayState::parse(state) else {
+                    warn!("skipping sofia gateway with unknown state {:?}", line);
+                    continue;
+                };
+                status.gateways.push(GatewaySummary {
+                    profile: profile.to_string(),
+                    name: gateway.to_string(),
+                    url: data.to_string(),
+                    state,
+                });
+            }
+            "alias" => status.aliases.push((name.to_string(), data.to_string())),
+            // header line
+            _ => {}
+        }
+    }
+    Ok(status)
+}
+
+/// Parses output of `sofia status profile <name>`
+pub fn parse_profile_status(output: &str) -> Result<ProfileStatus, EslError> {
+    let settings = parse_settings(output)?;
+    let setting = |key: &str| settings.get(key).cloned().unwrap_or_default();
+    let counter = |key: &str| -> Result<u64, EslError> {
+        match settings.get(key) {
+            Some(value) => Ok(value.parse()?),
+            None => Ok(0),
+        }
+    };
+    Ok(ProfileStatus {
+        name: setting("Name"),
+        url: setting("URL"),
+        calls_in: counter("CALLS-IN")?,
+        failed_calls_in: counter("FAILED-CALLS-IN")?,
+        calls_out: counter("CALLS-OUT")?,
+        failed_calls_out: counter("FAILED-CALLS-OUT")?,
+        settings,
+    })
+}
+
+/// Parses output of `sofia status gateway <name>`
+pub fn parse_gateway_status(output: &str) -> Result<GatewayStatus, EslError> {
+    let settings = parse_settings(output)?;
+    let setting = |key: &str| settings.get(key).cloned().unwrap_or_default();
+    let state = setting("State");
+    let state = GatewayState::parse(&state)
+        .ok_or_else(|| EslError::ProtocolError(format!("unknown gateway state {}", state)))?;
+    Ok(GatewayStatus {
+        name: setting("Name"),
+        profile: setting("Profile"),
+        state,
+        status: setting("Status"),
+        settings,
+    })
+}
+
+/// Parses output of `sofia status profile <name> reg`
+pub fn parse_sofia_registrations(output: &str) -> Vec<SofiaRegistration> {
+    let mut registrations = Vec::new();
+    let mut current: Option<SofiaRegistration> = None;
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        let key = key.trim();
+        if key == "Call-ID" {
+            registrations.extend(current.take());
+            current = Some(SofiaRegistration::default());
+        }
+        let Some(registration) = current.as_mut() else {
+            continue;
+        };
+        match key {
+            "Call-ID" => registration.call_id = value,
+            "User" => registration.user = value,
+            "Contact" => registration.contact = value,
+            "Agent" => registration.agent = value,
+            "Status" => registration.status = value,
+            "Ping-Status" => registration.ping_status = value,
+            "Host" => registration.host = value,
+            "IP" => registration.ip = value,
+            "Port" => registration.port = value,
+            "Auth-User" => registration.auth_user = value,
+            "Auth-Realm" => registration.auth_realm = value,
+            "MWI-Account" => registration.mwi_account = value,
+            _ => {}
+        }
+    }
+    registrations.extend(current);
+    registrations
+}
+
+impl EslConnection {
+    /// returns profiles, gateways and aliases from `sofia status`
+    pub async fn sofia_status(&self) -> Result<SofiaStatus, EslError> {
+        parse_sofia_status(&self.api("sofia status").await?)
+    }
+
+    /// returns settings and counters of profile from `sofia status profile <name>`
+    pub async fn sofia_profile(&self, profile: &str) -> Result<ProfileStatus, EslError> {
+        parse_profile_status(
+            &self
+                .api(&format!("sofia status profile {}", profile))
+                .await?,
+        )
+    }
+
+    /// returns registrations on profile from `sofia status profile <name> reg`
+    pub async fn sofia_registrations(
+        &self,
+        profile: &str,
+    ) -> Result<Vec<SofiaRegistration>, EslError> {
+        let output = self
+            .api(&format!("sofia status profile {} reg", profile))
+            .await?;
+        Ok(parse_sofia_registrations(&output))
+    }
+
+    /// returns state of gateway from `sofia status gateway <name>`
+    pub async fn sofia_gateway(&self, gateway: &str) -> Result<GatewayStatus, EslError> {
+        parse_gateway_status(
+            &self
+                .api(&format!("sofia status gateway {}", gateway))
+                .await?,
+        )
+    }
+
+    /// starts tracking state of every gateway
+    ///
+    /// Polls `sofia status` at given interval and applies `sofia::gateway_state`
+    /// events in between, `sofia::register` events trigger poll right away.
+    pub async fn watch_gateways(&self, interval: Duration) -> Result<GatewayWatcher, EslError> {
+        let mut events = self.events_matching(EventPredicate::new().custom(|event| {
+            matches!(
+                event.subclass(),
+                Some("sofia::gateway_state") | Some("sofia::register")
+            )
+        }));
+        self.subscribe(vec!["CUSTOM", "sofia::gateway_state", "sofia::register"])
+            .await?;
+        let connection = self.handle();
+        let gateways = Tracked::spawn(HashMap::new(), |tx| async move {
+            let mut poll = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = poll.tick() => {}
+                    event = events.next() => match event {
+                        Some(event) if event.subclass() == Some("sofia::register") => {
+                            poll.reset_immediately();
+                            continue;
+                        }
+                        Some(event) => {
+                            apply_gateway_event(&tx, &event);
+                            continue;
+                        }
+                        None => return,
+                    },
+                }
+                match connection.sofia_status().await {
+                    Ok(status) => {
+                        let gateways = status
+                            .gateways
+                            .into_iter()
+                            .map(|gateway| (gateway.name, gateway.state))
+                            .collect();
+                        tx.send_replace(gateways);
+                    }
+                    Err(e) => warn!("failed to poll sofia status: {}", e),
+                }
+            }
+        });
+        Ok(GatewayWatcher { gateways })
+    }
+}
+
+fn apply_gateway_event(tx: &watch::Sender<HashMap<String, GatewayState>>, event: &Event) {
+    let (Some(gateway), Some(state)) = (
+        event.header("Gateway"),
+        event.header("State").and_then(GatewayState::parse),
+    ) else {
+        return;
+    };
+    tx.send_if_modified(|gateways| gateways.insert(gateway.to_string(), state) != Some(state));
+}
+
+#[derive(Debug)]
+/// Live map of gateway states kept up to date by [`EslConnection::watch_gateways`]
+///
+/// Polling stops when watcher is dropped.
+pub struct GatewayWatcher {
+    gateways: Tracked<HashMap<String, GatewayState>>,
+}
+
+impl GatewayWatcher {
+    /// returns current state of every known gateway
+    pub fn gateways(&self) -> HashMap<String, GatewayState> {
+        self.gateways.borrow().clone()
+    }
+
+    /// returns current state of gateway
+    pub fn gateway(&self, name: &str) -> Option<GatewayState> {
+        self.gateways.borrow().get(name).copied()
+    }
+
+    /// waits until state of any gateway changes, fails once connection is closed
+    pub async fn changed(&mut self) -> Result<(), EslError> {
+        self.gateways.changed().await
+    }
+}
@@ -0,0 +1,45 @@
+use std::future::Future;
+
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::EslError;
+
+#[derive(Debug)]
+/// State kept up to date by background task, shared by gateway, conference and channel trackers
+///
+/// Task gets sender of state and runs until connection is closed, it is aborted
+/// when tracker is dropped.
+pub(crate) struct Tracked<T> {
+    state: watch::Receiver<T>,
+    task: JoinHandle<()>,
+}
+
+impl<T> Tracked<T> {
+    pub(crate) fn spawn<F>(initial: T, run: impl FnOnce(watch::Sender<T>) -> F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let (tx, state) = watch::channel(initial);
+        let task = tokio::spawn(run(tx));
+        Self { state, task }
+    }
+
+    pub(crate) fn borrow(&self) -> watch::Ref<'_, T> {
+        self.state.borrow()
+    }
+
+    /// waits for next update, failing once task stopped because connection was closed
+    pub(crate) async fn changed(&mut self) -> Result<(), EslError> {
+        self.state
+            .changed()
+            .await
+            .map_err(|_| EslError::Disconnected)
+    }
+}
+
+impl<T> Drop for Tracked<T> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
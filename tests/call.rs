@@ -0,0 +1,84 @@
+mod common;
+
+use common::connect_inbound;
+use freeswitch_esl::{EslError, EventName, HangupCause};
+
+#[tokio::test]
+async fn call_handle_executes_on_uuid() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    assert_eq!(Err(EslError::NoCallUuid), inbound.answer().await);
+
+    let call = inbound.call("call-1");
+    assert_eq!("call-1", call.uuid());
+    let playback = call.playback("ivr/ivr-welcome.wav");
+    let reply = async {
+        let command = session.read_command().await;
+        let mut lines = command.lines();
+        assert_eq!(Some("sendmsg call-1"), lines.next());
+        assert!(command.contains("execute-app-name: playback\n"));
+        assert!(command.contains("execute-app-arg: ivr/ivr-welcome.wav\n"));
+        let event_uuid = lines
+            .find_map(|line| line.strip_prefix("Event-UUID: "))
+            .unwrap();
+        session.reply("+OK").await;
+        let event = format!(
+            r#"{{"Event-Name":"CHANNEL_EXECUTE_COMPLETE","Unique-ID":"call-1","Application":"playback","Application-UUID":"{}"}}"#,
+            event_uuid
+        );
+        session.event("text/event-json", &event).await;
+    };
+    let (response, _) = tokio::join!(playback, reply);
+    assert_eq!(
+        Some(EventName::ChannelExecuteComplete),
+        response?.event_name()
+    );
+
+    let hangup = call.hangup("NORMAL_CLEARING");
+    let reply = async {
+        session.read_command().await;
+        session.reply("-ERR invalid session id [call-1]").await;
+    };
+    let (response, _) = tokio::join!(hangup, reply);
+    assert_eq!(
+        Err(EslError::ApiError("invalid session id [call-1]".into())),
+        response
+    );
+
+    drop(call);
+    assert!(inbound.connected());
+    Ok(())
+}
+
+#[tokio::test]
+async fn failed_bridge_returns_hangup_cause() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let call = inbound.call("call-1");
+    let bridge = call.bridge("user/1001");
+    let reply = async {
+        let command = session.read_command().await;
+        assert!(command.contains("execute-app-name: bridge\n"));
+        let event_uuid = command
+            .lines()
+            .find_map(|line| line.strip_prefix("Event-UUID: "))
+            .unwrap();
+        session.reply("+OK").await;
+        let event = format!(
+            r#"{{"Event-Name":"CHANNEL_EXECUTE_COMPLETE","Unique-ID":"call-1","Application":"bridge","Application-UUID":"{}","variable_originate_disposition":"NO_ANSWER"}}"#,
+            event_uuid
+        );
+        session.event("text/event-json", &event).await;
+    };
+    let (response, _) = tokio::join!(bridge, reply);
+    assert_eq!(
+        Err(EslError::CallFailed(HangupCause::NoAnswer)),
+        response.map(|_| ())
+    );
+    assert_eq!(HangupCause::NoAnswer, HangupCause::from_code(19).unwrap());
+    for cause in [HangupCause::NoAnswer, HangupCause::RejectAll] {
+        assert_eq!(Some(cause), HangupCause::parse(cause.as_str()));
+        assert_eq!(Some(cause), HangupCause::from_code(cause.code()));
+    }
+    assert_eq!(625, HangupCause::RejectAll.code());
+    Ok(())
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use common::connect_inbound;
+use freeswitch_esl::{Cdr, CdrConfig, CdrSink, CsvSink, EslError, JsonLinesSink};
+
+const A_LEG: &str = r#"{"Event-Name":"CHANNEL_HANGUP_COMPLETE","Unique-ID":"a","Call-Direction":"inbound","Caller-Caller-ID-Name":"Doe, John","Caller-Caller-ID-Number":"1000","Caller-Destination-Number":"2000","Other-Type":"originatee","Other-Leg-Unique-ID":"b","Hangup-Cause":"NORMAL_CLEARING","variable_start_uepoch":"1700000000000000","variable_answer_uepoch":"1700000005000000","variable_end_uepoch":"1700000065000000","variable_duration":"65","variable_billsec":"60","variable_sip_from_host":"example.com"}"#;
+const B_LEG: &str = r#"{"Event-Name":"CHANNEL_HANGUP_COMPLETE","Unique-ID":"b","Call-Direction":"outbound","Other-Type":"originator","Other-Leg-Unique-ID":"a","Hangup-Cause":"NORMAL_CLEARING","variable_answer_uepoch":"0","variable_billsec":"0"}"#;
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl SharedBuffer {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+#[tokio::test]
+async fn records_are_written_to_sinks() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let json = SharedBuffer::default();
+    let csv = SharedBuffer::default();
+    let config = CdrConfig::new()
+        .variable("sip_from_host")
+        .sink(JsonLinesSink::new(json.clone()))
+        .sink(CsvSink::new(csv.clone(), vec!["sip_from_host".into()]));
+    let recorder = inbound.record_cdrs(config);
+    let reply = async {
+        session
+            .expect(
+                "event json CHANNEL_HANGUP_COMPLETE",
+                "+OK event listener enabled json",
+            )
+            .await;
+    };
+    let (recorder, _) = tokio::join!(recorder, reply);
+    let recorder = recorder?;
+
+    session.event("text/event-json", A_LEG).await;
+    session.event("text/event-json", B_LEG).await;
+    session.close().await;
+    recorder.finished().await;
+
+    let lines: Vec<serde_json::Value> = json
+        .contents()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(2, lines.len());
+    assert_eq!("a", lines[0]["uuid"]);
+    assert_eq!("A", lines[0]["leg"]);
+    assert_eq!("b", lines[0]["other_leg_uuid"]);
+    assert_eq!(1700000005, lines[0]["answer"]);
+    assert_eq!(60, lines[0]["billsec"]);
+    assert_eq!("NORMAL_CLEARING", lines[0]["hangup_cause"]);
+    assert_eq!("example.com", lines[0]["variables"]["sip_from_host"]);
+    assert_eq!("B", lines[1]["leg"]);
+    assert_eq!("a", lines[1]["other_leg_uuid"]);
+    assert!(lines[1]["answer"].is_null());
+
+    let csv = csv.contents();
+    let mut rows = csv.lines();
+    assert_eq!(
+        Some("uuid,leg,other_leg_uuid,direction,caller_id_name,caller_id_number,destination_number,callee_id_name,callee_id_number,start,answer,end,duration,billsec,hangup_cause,sip_from_host"),
+        rows.next()
+    );
+    assert_eq!(
+        Some("a,A,b,inbound,\"Doe, John\",1000,2000,,,1700000000,1700000005,1700000065,65,60,NORMAL_CLEARING,example.com"),
+        rows.next()
+    );
+    assert_eq!(
+        Some("b,B,a,outbound,,,,,,,,,0,0,NORMAL_CLEARING,"),
+        rows.next()
+    );
+    Ok(())
+}
+
+struct SlowSink(SharedBuffer);
+
+impl CdrSink for SlowSink {
+    fn write(&mut self, cdr: &Cdr) -> Result<(), EslError> {
+        std::thread::sleep(Duration::from_millis(300));
+        writeln!(self.0, "{}", cdr.uuid)?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn slow_sink_does_not_block_connection() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let written = SharedBuffer::default();
+    let recorder = inbound.record_cdrs(CdrConfig::new().sink(SlowSink(written.clone())));
+    let reply = async {
+        session
+            .expect(
+                "event json CHANNEL_HANGUP_COMPLETE",
+                "+OK event listener enabled json",
+            )
+            .await;
+    };
+    let (recorder, _) = tokio::join!(recorder, reply);
+    let recorder = recorder?;
+
+    session.event("text/event-json", A_LEG).await;
+    let api = inbound.api_with_timeout("status", Duration::from_millis(200));
+    let reply = async {
+        assert_eq!("api status", session.read_command().await);
+        session.api_response("+OK UP\n").await;
+    };
+    let (response, _) = tokio::join!(api, reply);
+    assert_eq!(Ok("UP".into()), response);
+
+    session.close().await;
+    recorder.finished().await;
+    assert_eq!("a\n", written.contents());
+    Ok(())
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use common::connect_inbound;
+use freeswitch_esl::{ChannelChange, EslError, HangupCause};
+use futures::StreamExt;
+
+#[tokio::test]
+async fn tracks_channels_from_events() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let tracker = inbound.track_channels(true);
+    let reply = async {
+        session
+            .expect(
+                "event json CHANNEL_CREATE CHANNEL_STATE CHANNEL_CALLSTATE CHANNEL_ANSWER CHANNEL_BRIDGE CHANNEL_UNBRIDGE CHANNEL_HOLD CHANNEL_UNHOLD CHANNEL_HANGUP CHANNEL_DESTROY",
+                "+OK event listener enabled json",
+            )
+            .await;
+        assert_eq!("api show channels as json", session.read_command().await);
+        session
+            .api_response(concat!(
+                r#"{"row_count":1,"rows":[{"uuid":"old","direction":"inbound","created_epoch":"1700000000","state":"CS_EXECUTE","callstate":"ACTIVE","cid_num":"1000"}]}"#,
+                "\n"
+            ))
+            .await;
+    };
+    let (tracker, _) = tokio::join!(tracker, reply);
+    let mut tracker = tracker?;
+    let mut changes = tracker.changes();
+    assert_eq!(1, tracker.len());
+    assert_eq!("1000", tracker.channel("old").unwrap().caller_id_number);
+
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CHANNEL_CREATE","Unique-ID":"a","Call-Direction":"inbound","Channel-State":"CS_INIT","Channel-Call-State":"RINGING","Caller-Caller-ID-Number":"1001","Caller-Destination-Number":"2000","Caller-Channel-Created-Time":"1700000001000000","Caller-Channel-Answered-Time":"0","variable_sip_from_host":"example.com"}"#,
+        )
+        .await;
+    let change = changes.next().await.unwrap();
+    assert!(matches!(change, ChannelChange::Created(_)));
+    let channel = change.channel();
+    assert_eq!("RINGING", channel.call_state);
+    assert_eq!("2000", channel.destination_number);
+    assert!(channel.created.is_some());
+    assert!(channel.answered.is_none());
+    assert_eq!(
+        Some("example.com"),
+        channel.variables.get("sip_from_host").map(String::as_str)
+    );
+
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CHANNEL_ANSWER","Unique-ID":"a","Channel-Call-State":"ACTIVE","Caller-Channel-Answered-Time":"1700000002000000"}"#,
+        )
+        .await;
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CHANNEL_BRIDGE","Unique-ID":"a","Other-Leg-Unique-ID":"b","Caller-Callee-ID-Number":"2000"}"#,
+        )
+        .await;
+    changes.next().await.unwrap();
+    let change = changes.next().await.unwrap();
+    assert!(matches!(change, ChannelChange::Updated(_)));
+    let channel = tracker.channel("a").unwrap();
+    assert_eq!("ACTIVE", channel.call_state);
+    assert!(channel.answered.is_some());
+    assert_eq!(Some("b".to_string()), channel.bridged_to);
+    assert_eq!("2000", channel.callee_id_number);
+
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CHANNEL_DESTROY","Unique-ID":"a","Hangup-Cause":"NORMAL_CLEARING"}"#,
+        )
+        .await;
+    let change = changes.next().await.unwrap();
+    assert!(matches!(change, ChannelChange::Destroyed(_)));
+    assert_eq!(
+        Some(HangupCause::NormalClearing),
+        change.channel().hangup_cause
+    );
+    assert!(tracker.channel("a").is_none());
+    assert_eq!(1, tracker.len());
+
+    session.close().await;
+    assert!(changes.next().await.is_none());
+    while tracker.changed().await.is_ok() {}
+    Ok(())
+}
+
+#[tokio::test]
+async fn bridge_links_both_legs() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let tracker = inbound.track_channels(false);
+    let reply = session.expect(
+        "event json CHANNEL_CREATE CHANNEL_STATE CHANNEL_CALLSTATE CHANNEL_ANSWER CHANNEL_BRIDGE CHANNEL_UNBRIDGE CHANNEL_HOLD CHANNEL_UNHOLD CHANNEL_HANGUP CHANNEL_DESTROY",
+        "+OK event listener enabled json",
+    );
+    let (tracker, _) = tokio::join!(tracker, reply);
+    let tracker = tracker?;
+    let mut changes = tracker.changes();
+
+    for uuid in ["a", "b"] {
+        session
+            .event(
+                "text/event-json",
+                &format!(
+                    r#"{{"Event-Name":"CHANNEL_CREATE","Unique-ID":"{}"}}"#,
+                    uuid
+                ),
+            )
+            .await;
+        changes.next().await.unwrap();
+    }
+
+    // freeswitch fires bridge events on A-leg only
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CHANNEL_BRIDGE","Unique-ID":"a","Bridge-A-Unique-ID":"a","Bridge-B-Unique-ID":"b","Other-Leg-Unique-ID":"b"}"#,
+        )
+        .await;
+    let a_leg = changes.next().await.unwrap();
+    let b_leg = changes.next().await.unwrap();
+    assert_eq!(Some("b"), a_leg.channel().bridged_to.as_deref());
+    assert!(matches!(b_leg, ChannelChange::Updated(_)));
+    assert_eq!("b", b_leg.channel().uuid);
+    assert_eq!(Some("a"), b_leg.channel().bridged_to.as_deref());
+    assert_eq!(
+        Some("a".to_string()),
+        tracker.channel("b").unwrap().bridged_to
+    );
+
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CHANNEL_UNBRIDGE","Unique-ID":"a","Bridge-A-Unique-ID":"a","Bridge-B-Unique-ID":"b"}"#,
+        )
+        .await;
+    changes.next().await.unwrap();
+    changes.next().await.unwrap();
+    assert!(tracker.channel("a").unwrap().bridged_to.is_none());
+    assert!(tracker.channel("b").unwrap().bridged_to.is_none());
+    Ok(())
+}
@@ -0,0 +1,167 @@
+#![allow(dead_code)]
+
+use std::net::SocketAddr;
+
+use freeswitch_esl::{Esl, EslConnection};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Minimal fake freeswitch used to drive the client over a real socket
+pub struct MockServer {
+    listener: TcpListener,
+}
+
+impl MockServer {
+    pub async fn bind() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        Self { listener }
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.listener.local_addr().unwrap()
+    }
+
+    pub async fn accept(&self) -> MockSession {
+        let (stream, _) = self.listener.accept().await.unwrap();
+        MockSession {
+            stream: BufReader::new(stream),
+        }
+    }
+
+    /// Accepts inbound connection and answers auth and initial subscription
+    pub async fn accept_inbound(&self) -> MockSession {
+        let mut session = self.accept().await;
+        session.send("Content-Type: auth/request\n\n").await;
+        let auth = session.read_command().await;
+        assert!(auth.starts_with("auth "), "unexpected command {:?}", auth);
+        session.reply("+OK accepted").await;
+        let subscribe = session.read_command().await;
+        assert!(
+            subscribe.starts_with("event "),
+            "unexpected command {:?}",
+            subscribe
+        );
+        session.reply("+OK event listener enabled").await;
+        session
+    }
+}
+
+/// Connects inbound client to new mock server and answers handshake
+pub async fn connect_inbound() -> (EslConnection, MockSession) {
+    let server = MockServer::bind().await;
+    let (inbound, session) = tokio::join!(
+        Esl::inbound(server.addr(), "ClueCon"),
+        server.accept_inbound()
+    );
+    (inbound.unwrap(), session)
+}
+
+pub struct MockSession {
+    stream: BufReader<TcpStream>,
+}
+
+impl MockSession {
+    /// Reads one command terminated by an empty line
+    pub async fn read_command(&mut self) -> String {
+        let mut command = String::new();
+        loop {
+            let mut line = String::new();
+            let read = self.stream.read_line(&mut line).await.unwrap();
+            if read == 0 || line == "\n" {
+                break;
+            }
+            command.push_str(&line);
+        }
+        command.trim_end().to_string()
+    }
+
+    /// Reads body of given length which follows command
+    pub async fn read_body(&mut self, length: usize) -> String {
+        let mut body = vec![0; length];
+        self.stream.read_exact(&mut body).await.unwrap();
+        String::from_utf8(body).unwrap()
+    }
+
+    pub async fn send(&mut self, data: &str) {
+        self.stream
+            .get_mut()
+            .write_all(data.as_bytes())
+            .await
+            .unwrap();
+    }
+
+    pub async fn reply(&mut self, reply_text: &str) {
+        self.send(&format!(
+            "Content-Type: command/reply\nReply-Text: {}\n\n",
+            reply_text
+        ))
+        .await;
+    }
+
+    /// Asserts next command and answers it with command reply
+    pub async fn expect(&mut self, command: &str, reply_text: &str) {
+        assert_eq!(command, self.read_command().await);
+        self.reply(reply_text).await;
+    }
+
+    pub async fn api_response(&mut self, body: &str) {
+        self.send(&format!(
+            "Content-Type: api/response\nContent-Length: {}\n\n{}",
+            body.len(),
+            body
+        ))
+        .await;
+    }
+
+    /// Sends event with body in given `text/event-*` content type
+    pub async fn event(&mut self, content_type: &str, body: &str) {
+        self.send(&format!(
+            "Content-Length: {}\nContent-Type: {}\n\n{}",
+            body.len(),
+            content_type,
+            body
+        ))
+        .await;
+    }
+
+    pub async fn close(mut self) {
+        self.stream.get_mut().shutdown().await.unwrap();
+    }
+}
+
+/// Connects to outbound server like freeswitch does and answers handshake
+pub async fn connect_outbound(addr: SocketAddr, uuid: &str) -> MockSession {
+    connect_outbound_with(addr, uuid, "").await
+}
+
+/// Completes outbound handshake, adding given header lines to `connect` reply
+pub async fn connect_outbound_with(addr: SocketAddr, uuid: &str, headers: &str) -> MockSession {
+    let mut session = reply_to_connect(addr, uuid, headers).await;
+    let subscribe = session.read_command().await;
+    assert!(
+        subscribe.starts_with("event "),
+        "unexpected command {:?}",
+        subscribe
+    );
+    session.reply("+OK event listener enabled json").await;
+    assert_eq!("myevents", session.read_command().await);
+    session.reply("+OK Events Enabled").await;
+    session
+}
+
+/// Connects like `socket` application and replies to `connect` only
+pub async fn reply_to_connect(addr: SocketAddr, uuid: &str, headers: &str) -> MockSession {
+    let stream = TcpStream::connect(addr).await.unwrap();
+    let mut session = MockSession {
+        stream: BufReader::new(stream),
+    };
+    assert_eq!("connect", session.read_command().await);
+    session
+        .send(&format!(
+            "Content-Type: command/reply\nReply-Text: +OK\nChannel-Unique-ID: {uuid}\nUnique-ID: {uuid}\nCaller-Caller-ID-Number: 1000\nCaller-Destination-Number: 5000\n{headers}\n",
+            uuid = uuid,
+            headers = headers
+        ))
+        .await;
+    session
+}
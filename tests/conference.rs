@@ -0,0 +1,136 @@
+mod common;
+
+use common::connect_inbound;
+use freeswitch_esl::{EslError, MemberTarget};
+
+const JSON_LIST: &str = r#"[{"conference_name":"3000","member_count":2,"locked":false,"members":[{"type":"caller","id":1,"flags":{"can_hear":true,"can_speak":true,"talking":false,"has_floor":true,"is_moderator":true},"uuid":"call-1","caller_id_name":"Alice","caller_id_number":"1000","energy":100},{"type":"caller","id":2,"flags":{"can_hear":true,"can_speak":false,"talking":false,"has_floor":false,"is_moderator":false},"uuid":"call-2","caller_id_name":"Bob","caller_id_number":"1001","energy":300}]}]
+"#;
+
+fn maintenance(action: &str, member_id: u32, extra: &str) -> String {
+    format!(
+        r#"{{"Event-Name":"CUSTOM","Event-Subclass":"conference::maintenance","Conference-Name":"3000","Action":"{}","Member-ID":"{}"{}}}"#,
+        action, member_id, extra
+    )
+}
+
+#[tokio::test]
+async fn conference_commands() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let conference = inbound.conference("3000");
+
+    let list = conference.list();
+    let reply = async {
+        assert_eq!(
+            "api conference 3000 json_list",
+            session.read_command().await
+        );
+        session.api_response(JSON_LIST).await;
+    };
+    let (state, _) = tokio::join!(list, reply);
+    let state = state?;
+    assert_eq!("3000", state.name);
+    assert_eq!(2, state.members.len());
+    assert!(state.members[&1].moderator);
+    assert!(!state.members[&2].can_speak);
+    assert_eq!(300, state.members[&2].energy_level);
+
+    let mute = conference.mute(MemberTarget::NonModerator);
+    let reply = async {
+        assert_eq!(
+            "api conference 3000 mute non_moderator",
+            session.read_command().await
+        );
+        session.api_response("OK mute 2\n").await;
+    };
+    let (response, _) = tokio::join!(mute, reply);
+    response?;
+
+    let kick = conference.kick(2);
+    let reply = async {
+        assert_eq!("api conference 3000 kick 2", session.read_command().await);
+        session.api_response("OK kicked 2\n").await;
+    };
+    let (response, _) = tokio::join!(kick, reply);
+    response?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn tracker_follows_maintenance_events() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let tracker = inbound.track_conferences();
+    let reply = async {
+        session
+            .expect(
+                "event json CUSTOM conference::maintenance",
+                "+OK event listener enabled json",
+            )
+            .await;
+        assert_eq!("api conference json_list", session.read_command().await);
+        session.api_response(JSON_LIST).await;
+    };
+    let (tracker, _) = tokio::join!(tracker, reply);
+    let mut tracker = tracker?;
+    assert_eq!(2, tracker.conference("3000").unwrap().members.len());
+
+    session
+        .event(
+            "text/event-json",
+            &maintenance("start-talking", 2, r#","Talking":"true","Speak":"true""#),
+        )
+        .await;
+    tracker.changed().await?;
+    let conference = tracker.conference("3000").unwrap();
+    assert_eq!(
+        vec![2],
+        conference
+            .talking()
+            .iter()
+            .map(|m| m.id)
+            .collect::<Vec<_>>()
+    );
+
+    session
+        .event(
+            "text/event-json",
+            &maintenance(
+                "add-member",
+                3,
+                r#","Unique-ID":"call-3","Caller-Caller-ID-Number":"1002","Energy-Level":"200""#,
+            ),
+        )
+        .await;
+    tracker.changed().await?;
+    let member = tracker.conference("3000").unwrap().members[&3].clone();
+    assert_eq!("call-3", member.uuid);
+    assert_eq!("1002", member.caller_id_number);
+    assert_eq!(200, member.energy_level);
+
+    session
+        .event(
+            "text/event-json",
+            &maintenance("floor-change", 3, r#","Old-ID":"1","New-ID":"3""#),
+        )
+        .await;
+    tracker.changed().await?;
+    let conference = tracker.conference("3000").unwrap();
+    assert!(conference.members[&3].has_floor);
+    assert!(!conference.members[&1].has_floor);
+
+    session
+        .event("text/event-json", &maintenance("del-member", 1, ""))
+        .await;
+    tracker.changed().await?;
+    assert!(!tracker.conference("3000").unwrap().members.contains_key(&1));
+
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CUSTOM","Event-Subclass":"conference::maintenance","Conference-Name":"3000","Action":"conference-destroy"}"#,
+        )
+        .await;
+    tracker.changed().await?;
+    assert!(tracker.conferences().is_empty());
+    Ok(())
+}
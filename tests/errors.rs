@@ -0,0 +1,73 @@
+mod common;
+
+use std::time::Duration;
+
+use common::connect_inbound;
+use freeswitch_esl::{ConnectionState, EslError};
+
+#[tokio::test]
+async fn malformed_event_is_reported_and_connection_continues() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let mut errors = inbound.errors();
+
+    session.event("text/event-json", "{not json").await;
+    assert!(matches!(
+        errors.recv().await,
+        Ok(EslError::ProtocolError(_))
+    ));
+
+    let api = inbound.api("status");
+    let reply = async {
+        assert_eq!("api status", session.read_command().await);
+        session.api_response("+OK UP\n").await;
+    };
+    let (response, _) = tokio::join!(api, reply);
+    assert_eq!(Ok("UP".into()), response);
+    Ok(())
+}
+
+#[tokio::test]
+async fn malformed_frame_fails_waiters_and_disconnects() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let mut errors = inbound.errors();
+
+    let api = inbound.api("status");
+    let reply = async {
+        assert_eq!("api status", session.read_command().await);
+        session.send("this line has no colon\n\n").await;
+    };
+    let (response, _) = tokio::join!(api, reply);
+    assert!(matches!(response, Err(EslError::ProtocolError(_))));
+    assert!(matches!(
+        errors.recv().await,
+        Ok(EslError::ProtocolError(_))
+    ));
+    assert!(!inbound.connected());
+    assert_eq!(ConnectionState::Disconnected, inbound.connection_state());
+    assert_eq!(Err(EslError::Disconnected), inbound.api("status").await);
+    Ok(())
+}
+
+#[tokio::test]
+async fn malformed_background_job_fails_its_waiter() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let bgapi = inbound.bgapi_with_timeout("status", Duration::from_secs(1));
+    let reply = async {
+        let command = session.read_command().await;
+        let job_uuid = command.split_once("Job-UUID: ").unwrap().1.to_string();
+        session.reply(&format!("+OK Job-UUID: {}", job_uuid)).await;
+        session
+            .event(
+                "text/event-json",
+                &format!(
+                    r#"{{"Event-Name":"BACKGROUND_JOB","Job-UUID":"{}","_body":"+OK UP"#,
+                    job_uuid
+                ),
+            )
+            .await;
+    };
+    let (response, _) = tokio::join!(bgapi, reply);
+    assert!(matches!(response, Err(EslError::ProtocolError(_))));
+    Ok(())
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use common::connect_inbound;
+use freeswitch_esl::{ChannelState, EslError, EventName, HangupCause};
+use futures::StreamExt;
+
+#[tokio::test]
+async fn typed_accessors() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let mut events = inbound.events();
+
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CHANNEL_HANGUP","Unique-ID":"abc","Channel-State":"CS_HANGUP","Caller-Caller-ID-Number":"1000","Hangup-Cause":"USER_BUSY","Event-Date-Timestamp":"1700000000000000","variable_sip_from_host":"example.com"}"#,
+        )
+        .await;
+    let event = events.next().await.unwrap();
+    assert_eq!(Some(EventName::ChannelHangup), event.event_name());
+    assert_eq!(Some("abc"), event.unique_id());
+    assert_eq!(Some(ChannelState::Hangup), event.channel_state());
+    assert_eq!(Some("1000"), event.caller_id_number());
+    assert_eq!(Some(HangupCause::UserBusy), event.hangup_cause());
+    assert_eq!(Some("example.com"), event.variable("sip_from_host"));
+    assert_eq!(
+        Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+        event.timestamp()
+    );
+
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CUSTOM","Event-Subclass":"sofia::register"}"#,
+        )
+        .await;
+    let event = events.next().await.unwrap();
+    assert_eq!(
+        Some(EventName::Custom("sofia::register".into())),
+        event.event_name()
+    );
+    Ok(())
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use common::connect_inbound;
+use freeswitch_esl::{EslError, EventFormat};
+use futures::StreamExt;
+
+#[tokio::test]
+async fn plain_event() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let mut events = inbound.events();
+
+    let subscribe = inbound.subscribe_with_format(EventFormat::Plain, vec!["CUSTOM"]);
+    let reply = async {
+        assert_eq!("event plain CUSTOM", session.read_command().await);
+        session.reply("+OK event listener enabled plain").await;
+    };
+    let (response, _) = tokio::join!(subscribe, reply);
+    response?;
+    assert_eq!(EventFormat::Plain, inbound.event_format());
+
+    let body = "hello world";
+    let event = format!(
+        "Event-Name: CUSTOM\nEvent-Subclass: test%3A%3Aevent\nCaller-Caller-ID-Name: John%20Doe\nContent-Length: {}\n\n{}",
+        body.len(),
+        body
+    );
+    session.event("text/event-plain", &event).await;
+    let event = events.next().await.unwrap();
+    assert_eq!(Some("test::event"), event.header("Event-Subclass"));
+    assert_eq!(Some("John Doe"), event.header("Caller-Caller-ID-Name"));
+    assert_eq!(Some("hello world"), event.body().as_deref());
+    Ok(())
+}
+
+#[tokio::test]
+async fn xml_event() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let mut events = inbound.events();
+
+    let event = "<event>\n  <headers>\n    <Event-Name>HEARTBEAT</Event-Name>\n    <Up-Time>0%20years</Up-Time>\n    <Event-Info>System%20Ready &amp; ok</Event-Info>\n  </headers>\n</event>";
+    session.event("text/event-xml", event).await;
+    let event = events.next().await.unwrap();
+    assert_eq!(Some("HEARTBEAT"), event.header("Event-Name"));
+    assert_eq!(Some("0 years"), event.header("Up-Time"));
+    assert_eq!(Some("System Ready & ok"), event.header("Event-Info"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn events_do_not_consume_command_replies() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let api = inbound.api("reloadxml");
+    let reply = async {
+        assert_eq!("api reloadxml", session.read_command().await);
+        session
+            .event("text/event-plain", "Event-Name: HEARTBEAT\n\n")
+            .await;
+        session
+            .event(
+                "text/event-xml",
+                "<event><headers><Event-Name>HEARTBEAT</Event-Name></headers></event>",
+            )
+            .await;
+        session.api_response("+OK [Success]\n").await;
+    };
+    let (response, _) = tokio::join!(api, reply);
+    assert_eq!(Ok("[Success]".into()), response);
+    Ok(())
+}
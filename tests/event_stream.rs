@@ -0,0 +1,99 @@
+mod common;
+
+use common::connect_inbound;
+use freeswitch_esl::{EslError, EventName, EventPredicate};
+use futures::StreamExt;
+
+#[tokio::test]
+async fn subscribers_receive_events_independently() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let mut all = inbound.events();
+    let mut hangups = inbound.events_matching(
+        EventPredicate::new()
+            .name(EventName::ChannelHangup)
+            .unique_id("abc"),
+    );
+    let mut registrations =
+        inbound.events_matching(EventPredicate::new().subclass("sofia::register"));
+
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CHANNEL_HANGUP","Unique-ID":"other"}"#,
+        )
+        .await;
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CUSTOM","Event-Subclass":"sofia::register"}"#,
+        )
+        .await;
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CHANNEL_HANGUP","Unique-ID":"abc"}"#,
+        )
+        .await;
+
+    let event = all.next().await.unwrap();
+    assert_eq!(Some("other"), event.unique_id());
+    let event = all.next().await.unwrap();
+    assert_eq!(Some("sofia::register"), event.subclass());
+    let event = all.next().await.unwrap();
+    assert_eq!(Some("abc"), event.unique_id());
+
+    let event = hangups.next().await.unwrap();
+    assert_eq!(Some(EventName::ChannelHangup), event.event_name());
+    assert_eq!(Some("abc"), event.unique_id());
+
+    let event = registrations.next().await.unwrap();
+    assert_eq!(Some("sofia::register"), event.subclass());
+
+    session.close().await;
+    assert!(all.next().await.is_none());
+    assert!(hangups.next().await.is_none());
+    assert!(inbound.events().next().await.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn channel_events_end_after_destroy() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let myevents = inbound.myevents("call-1");
+    let reply = async {
+        assert_eq!("myevents call-1 json", session.read_command().await);
+        session.reply("+OK Events Enabled").await;
+    };
+    let (channel, _) = tokio::join!(myevents, reply);
+    let mut channel = channel?;
+    let mut other = inbound.channel_events("call-2");
+
+    for (name, uuid) in [
+        ("CHANNEL_ANSWER", "call-2"),
+        ("CHANNEL_ANSWER", "call-1"),
+        ("CHANNEL_DESTROY", "call-1"),
+        ("CHANNEL_DESTROY", "call-2"),
+    ] {
+        let event = format!(r#"{{"Event-Name":"{}","Unique-ID":"{}"}}"#, name, uuid);
+        session.event("text/event-json", &event).await;
+    }
+
+    let names: Vec<_> = channel
+        .by_ref()
+        .filter_map(|e| async move { e.event_name() })
+        .collect()
+        .await;
+    assert_eq!(
+        vec![EventName::ChannelAnswer, EventName::ChannelDestroy],
+        names
+    );
+    assert!(channel.next().await.is_none());
+
+    let event = other.next().await.unwrap();
+    assert_eq!(Some(EventName::ChannelAnswer), event.event_name());
+    let event = other.next().await.unwrap();
+    assert_eq!(Some(EventName::ChannelDestroy), event.event_name());
+    assert!(other.next().await.is_none());
+    Ok(())
+}
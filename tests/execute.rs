@@ -0,0 +1,109 @@
+mod common;
+
+use std::time::Duration;
+
+use common::connect_inbound;
+use freeswitch_esl::{EslError, EventName, ExecuteOptions};
+
+#[tokio::test]
+async fn execute_options_are_sent_as_headers() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let call = inbound.call("call-1");
+
+    let options = ExecuteOptions::new()
+        .event_lock()
+        .loops(3)
+        .hold_bleg()
+        .asynchronous();
+    let execute = call.execute_with_options("playback", "tone_stream://%(200,0,500)", options);
+    let reply = async {
+        let command = session.read_command().await;
+        assert!(command.starts_with("sendmsg call-1\ncall-command: execute\n"));
+        for header in [
+            "event-lock: true",
+            "loops: 3",
+            "async: true",
+            "hold-bleg: true",
+        ] {
+            assert!(
+                command.contains(header),
+                "missing {} in {:?}",
+                header,
+                command
+            );
+        }
+        session.reply("+OK").await;
+    };
+    // async execute returns without waiting for CHANNEL_EXECUTE_COMPLETE
+    let (response, _) = tokio::join!(execute, reply);
+    assert_eq!(Some("+OK"), response?.header("Reply-Text"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn started_execute_can_be_cancelled() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let call = inbound.call("call-1");
+
+    let start = call.start_execute("playback", "ivr/ivr-welcome.wav", ExecuteOptions::new());
+    let reply = async {
+        let command = session.read_command().await;
+        assert!(!command.contains("async: true"));
+        session.reply("+OK").await;
+    };
+    let (handle, _) = tokio::join!(start, reply);
+    let handle = handle?;
+
+    let cancel = handle.cancel();
+    let reply = async {
+        assert_eq!("api uuid_break call-1 all", session.read_command().await);
+        session.api_response("+OK\n").await;
+        let event = format!(
+            r#"{{"Event-Name":"CHANNEL_EXECUTE_COMPLETE","Unique-ID":"call-1","Application":"playback","Application-UUID":"{}","Application-Response":"FILE PLAYED"}}"#,
+            handle.application_uuid()
+        );
+        session.event("text/event-json", &event).await;
+    };
+    let (cancelled, _) = tokio::join!(cancel, reply);
+    cancelled?;
+
+    let event = handle.wait().await?;
+    assert_eq!(Some(EventName::ChannelExecuteComplete), event.event_name());
+    Ok(())
+}
+
+#[tokio::test]
+async fn looped_execute_completes_after_last_loop() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let call = inbound.call("call-1");
+
+    let start = call.start_execute("playback", "beep.wav", ExecuteOptions::new().loops(3));
+    let reply = async {
+        assert!(session.read_command().await.contains("loops: 3"));
+        session.reply("+OK").await;
+    };
+    let (handle, _) = tokio::join!(start, reply);
+    let handle = handle?;
+    let event = format!(
+        r#"{{"Event-Name":"CHANNEL_EXECUTE_COMPLETE","Unique-ID":"call-1","Application":"playback","Application-UUID":"{}"}}"#,
+        handle.application_uuid()
+    );
+
+    let wait = handle.wait();
+    tokio::pin!(wait);
+    for _ in 0..2 {
+        session.event("text/event-json", &event).await;
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), &mut wait)
+                .await
+                .is_err(),
+            "resolved before last loop"
+        );
+    }
+    session.event("text/event-json", &event).await;
+    let event = tokio::time::timeout(Duration::from_secs(1), wait)
+        .await
+        .expect("not resolved after last loop")?;
+    assert_eq!(Some(EventName::ChannelExecuteComplete), event.event_name());
+    Ok(())
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{connect_inbound, MockServer};
+use freeswitch_esl::{ConnectionState, Esl, EslError, ReconnectConfig};
+
+#[tokio::test]
+async fn filters_are_tracked() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let (response, _) = tokio::join!(
+        inbound.filter("variable_domain_name", "tenant1.example.com"),
+        session.expect(
+            "filter variable_domain_name tenant1.example.com",
+            "+OK filter added. [variable_domain_name]=[tenant1.example.com]"
+        )
+    );
+    response?;
+    let (response, _) = tokio::join!(
+        inbound.filter("Event-Name", "CHANNEL_CREATE"),
+        session.expect("filter Event-Name CHANNEL_CREATE", "+OK filter added.")
+    );
+    response?;
+    assert_eq!(
+        vec![
+            (
+                "variable_domain_name".to_string(),
+                "tenant1.example.com".to_string()
+            ),
+            ("Event-Name".to_string(), "CHANNEL_CREATE".to_string())
+        ],
+        inbound.filters()
+    );
+
+    let (response, _) = tokio::join!(
+        inbound.filter_delete("Event-Name", None),
+        session.expect("filter delete Event-Name", "+OK filter deleted.")
+    );
+    response?;
+    assert_eq!(1, inbound.filters().len());
+
+    let (response, _) = tokio::join!(
+        inbound.filter_delete("Unique-ID", Some("missing")),
+        session.expect("filter delete Unique-ID missing", "-ERR invalid syntax")
+    );
+    assert_eq!(
+        Err(EslError::ApiError("invalid syntax".into())),
+        response.map(|_| ())
+    );
+
+    let (response, _) = tokio::join!(
+        inbound.nixevent(vec!["CHANNEL_EXECUTE_COMPLETE"]),
+        session.expect("nixevent CHANNEL_EXECUTE_COMPLETE", "+OK events nixed")
+    );
+    response?;
+    assert_eq!(
+        vec!["BACKGROUND_JOB".to_string()],
+        inbound.subscribed_events()
+    );
+
+    let (response, _) = tokio::join!(
+        inbound.noevents(),
+        session.expect("noevents", "+OK no longer listening for events")
+    );
+    response?;
+    assert!(inbound.subscribed_events().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn filters_are_restored_after_reconnect() -> Result<(), EslError> {
+    let server = MockServer::bind().await;
+    let addr = server.addr();
+    let config = ReconnectConfig::default().initial_delay(Duration::from_millis(10));
+    let (inbound, mut session) = tokio::join!(
+        Esl::inbound_with_reconnect(addr, "ClueCon", config),
+        server.accept_inbound()
+    );
+    let inbound = inbound?;
+    let mut states = inbound.state_changes();
+
+    let (response, _) = tokio::join!(
+        inbound.subscribe(vec!["CUSTOM", "sofia::register"]),
+        session.expect("event json CUSTOM sofia::register", "+OK")
+    );
+    response?;
+    let (response, _) = tokio::join!(
+        inbound.filter("variable_domain_name", "tenant1"),
+        session.expect("filter variable_domain_name tenant1", "+OK filter added.")
+    );
+    response?;
+    let (response, _) = tokio::join!(
+        inbound.divert_events(true),
+        session.expect("divert_events on", "+OK events diverted")
+    );
+    response?;
+    session.close().await;
+
+    let mut session = server.accept().await;
+    session.send("Content-Type: auth/request\n\n").await;
+    session.expect("auth ClueCon", "+OK accepted").await;
+    session
+        .expect(
+            "event json BACKGROUND_JOB CHANNEL_EXECUTE_COMPLETE CUSTOM sofia::register",
+            "+OK",
+        )
+        .await;
+    session
+        .expect("filter variable_domain_name tenant1", "+OK filter added.")
+        .await;
+    session
+        .expect("divert_events on", "+OK events diverted")
+        .await;
+
+    while *states.borrow_and_update() != ConnectionState::Connected {
+        states.changed().await.unwrap();
+    }
+    Ok(())
+}
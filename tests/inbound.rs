@@ -1,4 +1,4 @@
-use freeswitch_esl::{Esl, EslError};
+use freeswitch_esl::{Esl, EslError, HangupCause};
 
 #[tokio::test]
 async fn reloadxml() -> Result<(), EslError> {
@@ -17,7 +17,10 @@ async fn call_user_that_doesnt_exists() -> Result<(), EslError> {
         .api("originate user/some_user_that_doesnt_exists karan")
         .await
         .unwrap_err();
-    assert_eq!(EslError::ApiError("SUBSCRIBER_ABSENT".into()), response);
+    assert_eq!(
+        EslError::CallFailed(HangupCause::SubscriberAbsent),
+        response
+    );
     Ok(())
 }
 
@@ -49,7 +52,7 @@ async fn multiple_actions() -> core::result::Result<(), EslError> {
         .bgapi("originate user/some_user_that_doesnt_exists karan")
         .await;
     assert_eq!(
-        Err(EslError::ApiError("SUBSCRIBER_ABSENT".to_string())),
+        Err(EslError::CallFailed(HangupCause::SubscriberAbsent)),
         body
     );
     Ok(())
@@ -64,7 +67,10 @@ async fn concurrent_api() -> core::result::Result<(), EslError> {
     let response3 = inbound.api("reloadxml");
     let (result1, result2, result3) = tokio::join!(response1, response2, response3);
     assert_eq!(Ok("[Success]".into()), result1);
-    assert_eq!(Err(EslError::ApiError("SUBSCRIBER_ABSENT".into())), result2);
+    assert_eq!(
+        Err(EslError::CallFailed(HangupCause::SubscriberAbsent)),
+        result2
+    );
     assert_eq!(Ok("[Success]".into()), result3);
     Ok(())
 }
@@ -79,7 +85,7 @@ async fn concurrent_bgapi() -> core::result::Result<(), EslError> {
     let (response1, response2, response3) = tokio::join!(response1, response2, response3);
     assert_eq!(Ok("[Success]".to_string()), response1);
     assert_eq!(
-        Err(EslError::ApiError("SUBSCRIBER_ABSENT".to_string())),
+        Err(EslError::CallFailed(HangupCause::SubscriberAbsent)),
         response2
     );
     assert_eq!(Ok("[Success]".to_string()), response3);
@@ -90,7 +96,7 @@ async fn concurrent_bgapi() -> core::result::Result<(), EslError> {
 async fn connected_status() -> Result<(), EslError> {
     let addr = "localhost:8021";
     let inbound = Esl::inbound(addr, "ClueCon").await?;
-    assert_eq!(true, inbound.connected());
+    assert!(inbound.connected());
     Ok(())
 }
 
@@ -116,7 +122,7 @@ async fn uuid_kill() -> Result<(), EslError> {
         .api("originate {origination_uuid=karan}loopback/1000 &conference(karan)")
         .await?;
     assert_eq!("karan", uuid);
-    let uuid_kill_response = inbound.api(&format!("uuid_kill karan")).await?;
+    let uuid_kill_response = inbound.api("uuid_kill karan").await?;
     assert_eq!("", uuid_kill_response);
     Ok(())
 }
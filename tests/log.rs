@@ -0,0 +1,52 @@
+mod common;
+
+use common::connect_inbound;
+use freeswitch_esl::{EslError, LogLevel};
+use futures::StreamExt;
+
+#[tokio::test]
+async fn log_lines_are_not_taken_as_replies() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let mut logs = inbound.logs();
+
+    let log = inbound.log(LogLevel::Debug);
+    let reply = async {
+        session.expect("log DEBUG", "+OK log level DEBUG [7]").await;
+    };
+    let (log, _) = tokio::join!(log, reply);
+    log?;
+
+    let uuid = "4c882cc4-cd02-11e6-8b82-395b501876f9";
+    let line = "2024-01-01 10:00:00.000000 [DEBUG] switch_core_state_machine.c:424 State NEW\n";
+    let api = inbound.api("status");
+    let reply = async {
+        assert_eq!("api status", session.read_command().await);
+        session
+            .send(&format!(
+                "Content-Type: log/data\nContent-Length: {}\nLog-Level: 7\nText-Channel: 3\nLog-File: switch_core_state_machine.c\nLog-Func: switch_core_session_run\nLog-Line: 424\nUser-Data: {}\n\n{}",
+                line.len(),
+                uuid,
+                line
+            ))
+            .await;
+        session.api_response("UP 0 years\n").await;
+    };
+    let (status, _) = tokio::join!(api, reply);
+    assert_eq!("UP 0 years\n", status?);
+
+    let log = logs.next().await.unwrap();
+    assert_eq!(LogLevel::Debug, log.level);
+    assert_eq!("switch_core_state_machine.c", log.file);
+    assert_eq!(424, log.line);
+    assert_eq!("switch_core_session_run", log.function);
+    assert_eq!(Some(uuid), log.channel_uuid.as_deref());
+    assert_eq!(line.trim_end(), log.message);
+
+    let nolog = inbound.nolog();
+    let reply = async {
+        session.expect("nolog", "+OK no longer logging").await;
+    };
+    let (nolog, _) = tokio::join!(nolog, reply);
+    nolog?;
+    Ok(())
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use std::time::Duration;
+
+use common::connect_inbound;
+use freeswitch_esl::{Endpoint, EslError, HangupCause, Originate};
+
+#[test]
+fn originate_command() {
+    let originate = Originate::new(Endpoint::new("user/1000").variable("leg_timeout", 10))
+        .and(Endpoint::new("user/1001"))
+        .then(Endpoint::new("sofia/gateway/gw/1000"))
+        .uuid("abc")
+        .caller_id_name("John Doe")
+        .timeout(Duration::from_secs(30))
+        .variable("sip_h_X-Tags", "a,b")
+        .application("conference", "3000@default");
+    assert_eq!(
+        "originate {origination_uuid=abc,origination_caller_id_name='John Doe',originate_timeout=30,sip_h_X-Tags=a\\,b}[leg_timeout=10]user/1000,user/1001|sofia/gateway/gw/1000 &conference(3000@default)",
+        originate.to_string()
+    );
+
+    let originate = Originate::new(Endpoint::new("loopback/1000"))
+        .extension("2000")
+        .context("public");
+    assert_eq!(
+        "originate loopback/1000 2000 XML public",
+        originate.to_string()
+    );
+    assert_eq!(
+        "originate user/1000 &park()",
+        Originate::new(Endpoint::new("user/1000")).to_string()
+    );
+    assert_eq!(
+        "originate {originate_timeout=1}user/1000 &park()",
+        Originate::new(Endpoint::new("user/1000"))
+            .timeout(Duration::from_millis(500))
+            .to_string()
+    );
+}
+
+#[tokio::test]
+async fn originate_returns_uuid_or_hangup_cause() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let originate = Originate::new(Endpoint::new("user/1000"));
+
+    let call = inbound.originate(&originate);
+    let reply = async {
+        assert_eq!(
+            "api originate user/1000 &park()",
+            session.read_command().await
+        );
+        session.api_response("+OK 7f4de4bc-17d7-11ec\n").await;
+    };
+    let (response, _) = tokio::join!(call, reply);
+    assert_eq!(Ok("7f4de4bc-17d7-11ec".into()), response);
+
+    let call = inbound.originate(&originate);
+    let reply = async {
+        session.read_command().await;
+        session.api_response("-ERR USER_BUSY\n").await;
+    };
+    let (response, _) = tokio::join!(call, reply);
+    assert_eq!(Err(EslError::CallFailed(HangupCause::UserBusy)), response);
+    Ok(())
+}
@@ -0,0 +1,209 @@
+mod common;
+
+use common::{connect_outbound, connect_outbound_with, reply_to_connect};
+use freeswitch_esl::{Esl, EslError, EventName, ExecuteOptions, OutboundConfig};
+use futures::StreamExt;
+
+#[tokio::test]
+async fn linger_delivers_final_events() -> Result<(), EslError> {
+    let server = Esl::outbound("127.0.0.1:0").await?;
+    let addr = server.local_addr()?;
+    let (accepted, mut session) = tokio::join!(server.accept(), connect_outbound(addr, "call-1"));
+    let (conn, _) = accepted?;
+    let mut events = conn.events();
+
+    let linger = conn.linger(Some(10));
+    let reply = async {
+        assert_eq!("linger 10", session.read_command().await);
+        session.reply("+OK will linger").await;
+    };
+    let (response, _) = tokio::join!(linger, reply);
+    response?;
+
+    let playback = conn.playback("ivr/ivr-welcome.wav");
+    let hangup = async {
+        let command = session.read_command().await;
+        assert!(command.starts_with("sendmsg call-1\n"));
+        session.reply("+OK").await;
+        session
+            .send("Content-Type: text/disconnect-notice\nController-Session-ID: 1\nContent-Disposition: linger\nChannel-UUID: call-1\nContent-Length: 0\n\n")
+            .await;
+        session
+            .event(
+                "text/event-json",
+                r#"{"Event-Name":"CHANNEL_HANGUP_COMPLETE","Unique-ID":"call-1","Hangup-Cause":"NORMAL_CLEARING"}"#,
+            )
+            .await;
+        let event = events.next().await.unwrap();
+        assert_eq!(Some(EventName::ChannelHangupComplete), event.event_name());
+        assert!(conn.lingering());
+        assert!(conn.connected());
+        let body = "Disconnected, goodbye.\n";
+        session
+            .send(&format!(
+                "Content-Type: text/disconnect-notice\nContent-Disposition: disconnect\nContent-Length: {}\n\n{}",
+                body.len(),
+                body
+            ))
+            .await;
+        session.close().await;
+    };
+    let (response, _) = tokio::join!(playback, hangup);
+    assert_eq!(Err(EslError::Disconnected), response);
+    assert!(!conn.connected());
+    assert_eq!(
+        Err(EslError::Disconnected),
+        conn.nolinger().await.map(|_| ())
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_session_runs_concurrent_executes() -> Result<(), EslError> {
+    let server =
+        Esl::outbound_with_config("127.0.0.1:0", OutboundConfig::new().async_mode().full()).await?;
+    let addr = server.local_addr()?;
+    let (accepted, mut session) = tokio::join!(
+        server.accept(),
+        connect_outbound_with(addr, "call-1", "Socket-Mode: async\nControl: full\n")
+    );
+    let (conn, _) = accepted?;
+    assert_eq!(Some(server.config()), conn.outbound_config());
+    assert_eq!(Some("call-1".to_string()), conn.call_uuid().await);
+
+    let answer = conn.answer();
+    let playback = conn.playback("ivr/ivr-welcome.wav");
+    let replies = async {
+        let mut uuids = Vec::new();
+        for app in ["answer", "playback"] {
+            let command = session.read_command().await;
+            assert!(command.contains(&format!("execute-app-name: {}\n", app)));
+            assert!(command.contains("event-lock: true\n"));
+            let uuid = command
+                .lines()
+                .find_map(|line| line.strip_prefix("Event-UUID: "))
+                .unwrap()
+                .to_string();
+            session.reply("+OK").await;
+            uuids.push((app, uuid));
+        }
+        // completions are matched by Application-UUID, not by order
+        for (app, uuid) in uuids.iter().rev() {
+            let event = format!(
+                r#"{{"Event-Name":"CHANNEL_EXECUTE_COMPLETE","Unique-ID":"call-1","Application":"{}","Application-UUID":"{}"}}"#,
+                app, uuid
+            );
+            session.event("text/event-json", &event).await;
+        }
+    };
+    let (answer, playback, _) = tokio::join!(answer, playback, replies);
+    assert_eq!(Some("answer"), answer?.header("Application"));
+    assert_eq!(Some("playback"), playback?.header("Application"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn socket_mode_is_checked_and_event_lock_can_be_disabled() -> Result<(), EslError> {
+    let server =
+        Esl::outbound_with_config("127.0.0.1:0", OutboundConfig::new().async_mode()).await?;
+    let addr = server.local_addr()?;
+    let (accepted, _session) = tokio::join!(
+        server.accept(),
+        reply_to_connect(
+            addr,
+            "call-1",
+            "Socket-Mode: static\nControl: single-channel\n"
+        )
+    );
+    assert!(matches!(accepted, Err(EslError::ProtocolError(_))));
+
+    let (accepted, mut session) = tokio::join!(
+        server.accept(),
+        connect_outbound_with(
+            addr,
+            "call-2",
+            "Socket-Mode: async\nControl: single-channel\n"
+        )
+    );
+    let (conn, _) = accepted?;
+    let options = ExecuteOptions::new().without_event_lock();
+    let playback = conn.start_execute("playback", "ivr/ivr-welcome.wav", options);
+    let reply = async {
+        let command = session.read_command().await;
+        assert!(command.contains("event-lock: false\n"));
+        assert!(!command.contains("event-lock: true"));
+        session.reply("+OK").await;
+    };
+    let (response, _) = tokio::join!(playback, reply);
+    response?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn channel_data_follows_channel_events() -> Result<(), EslError> {
+    let server = Esl::outbound("127.0.0.1:0").await?;
+    let addr = server.local_addr()?;
+    // freeswitch sends values of connect reply url encoded
+    let (accepted, mut session) = tokio::join!(
+        server.accept(),
+        connect_outbound_with(
+            addr,
+            "call-1",
+            "Event-Name: CHANNEL_DATA\nCore-UUID: core-1\nCaller-Caller-ID-Name: John%20Doe\nvariable_sip_from_uri: 1000%40example.com\n"
+        )
+    );
+    let (conn, _) = accepted?;
+    let mut events = conn.events();
+
+    let data = conn.channel_data().unwrap();
+    assert_eq!(Some("call-1"), data.uuid());
+    assert_eq!(Some("John Doe"), data.caller_id_name());
+    assert_eq!(Some("1000@example.com"), data.variable("sip_from_uri"));
+    assert_eq!(None, data.header("Event-Name"));
+    assert_eq!(None, data.header("Core-UUID"));
+    assert_eq!(Some("1000"), data.caller_id_number());
+    assert_eq!(Some("5000"), data.destination_number());
+    assert_eq!(None, data.answer_state());
+    assert_eq!(None, data.header("Reply-Text"));
+
+    for uuid in ["call-2", "call-1"] {
+        let event = format!(
+            r#"{{"Event-Name":"CHANNEL_ANSWER","Event-Sequence":"7","Core-UUID":"core-1","Unique-ID":"{}","Answer-State":"answered","Caller-Context":"public","variable_sip_h_X-Account-Id":"{}"}}"#,
+            uuid, uuid
+        );
+        session.event("text/event-json", &event).await;
+        events.next().await.unwrap();
+    }
+
+    let data = conn.channel_data().unwrap();
+    assert_eq!(Some("answered"), data.answer_state());
+    assert_eq!(Some("public"), data.context());
+    assert_eq!(Some("call-1"), data.sip_header("X-Account-Id"));
+    assert_eq!(Some(&"call-1"), data.sip_headers().get("X-Account-Id"));
+    assert_eq!(Some("John Doe"), data.caller_id_name());
+    assert_eq!(None, data.header("Event-Sequence"));
+    assert_eq!(Some(&"call-1"), data.variables().get("sip_h_X-Account-Id"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn only_connect_reply_is_url_decoded() -> Result<(), EslError> {
+    let server = Esl::outbound("127.0.0.1:0").await?;
+    let addr = server.local_addr()?;
+    let (accepted, mut session) = tokio::join!(
+        server.accept(),
+        connect_outbound_with(addr, "call-1", "Caller-Caller-ID-Name: %41lice\n")
+    );
+    let (conn, _) = accepted?;
+    assert_eq!(Some("Alice"), conn.channel_data().unwrap().caller_id_name());
+
+    let options = ExecuteOptions::new().asynchronous();
+    let playback = conn.execute_with_options("playback", "ivr/ivr-welcome.wav", options);
+    let reply = async {
+        session.read_command().await;
+        session.reply("+OK %41").await;
+    };
+    let (response, _) = tokio::join!(playback, reply);
+    assert_eq!(Some("+OK %41"), response?.header("Reply-Text"));
+    Ok(())
+}
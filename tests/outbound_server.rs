@@ -0,0 +1,89 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::connect_outbound;
+use freeswitch_esl::{Esl, EslError, OutboundServer, Overflow};
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpStream;
+
+#[tokio::test]
+async fn queued_session_runs_once_previous_finishes() -> Result<(), EslError> {
+    let outbound = Esl::outbound("127.0.0.1:0").await?;
+    let addr = outbound.local_addr()?;
+    let server = OutboundServer::new(outbound)
+        .max_sessions(1)
+        .serve(|conn| async move { conn.api("status").await.map(|_| ()) });
+
+    let mut first = connect_outbound(addr, "call-1").await;
+    assert_eq!("api status", first.read_command().await);
+    let sessions = server.active_sessions();
+    assert_eq!(1, sessions.len());
+    assert_eq!(Some("call-1"), sessions[0].uuid.as_deref());
+
+    let second = tokio::spawn(connect_outbound(addr, "call-2"));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!second.is_finished());
+    assert_eq!(1, server.active_sessions().len());
+
+    first.api_response("UP 0 years").await;
+    let mut second = second.await.unwrap();
+    assert_eq!("api status", second.read_command().await);
+    assert_eq!(
+        vec![Some("call-2".to_string())],
+        server
+            .active_sessions()
+            .into_iter()
+            .map(|session| session.uuid)
+            .collect::<Vec<_>>()
+    );
+
+    let (report, _) = tokio::join!(
+        server.shutdown(Duration::from_secs(5)),
+        second.api_response("UP 0 years")
+    );
+    assert_eq!(1, report.drained.len());
+    assert_eq!(Some("call-2"), report.drained[0].uuid.as_deref());
+    assert!(report.cut.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn shutdown_cuts_sessions_past_deadline() -> Result<(), EslError> {
+    let outbound = Esl::outbound("127.0.0.1:0").await?;
+    let addr = outbound.local_addr()?;
+    // handler holds clone until it is dropped
+    let handler_alive = Arc::new(());
+    let handler_marker = Arc::clone(&handler_alive);
+    let server = OutboundServer::new(outbound)
+        .max_sessions(1)
+        .overflow(Overflow::Reject)
+        .serve(move |conn| {
+            let marker = Arc::clone(&handler_marker);
+            async move {
+                let _marker = marker;
+                conn.api("status").await.map(|_| ())
+            }
+        });
+
+    let mut first = connect_outbound(addr, "call-1").await;
+    assert_eq!("api status", first.read_command().await);
+
+    let mut rejected = TcpStream::connect(addr).await.unwrap();
+    let mut buf = [0; 16];
+    assert_eq!(0, rejected.read(&mut buf).await.unwrap());
+    assert_eq!(1, server.active_sessions().len());
+
+    let cut = async {
+        assert_eq!("exit", first.read_command().await);
+        first.api_response("UP 0 years").await;
+        first.reply("+OK bye").await;
+    };
+    let (report, _) = tokio::join!(server.shutdown(Duration::from_millis(50)), cut);
+    assert!(report.drained.is_empty());
+    assert_eq!(1, report.cut.len());
+    assert_eq!(Some("call-1"), report.cut[0].uuid.as_deref());
+    assert_eq!(1, Arc::strong_count(&handler_alive));
+    Ok(())
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use std::time::Duration;
+
+use common::MockServer;
+use freeswitch_esl::{ConnectionState, Esl, EslError, ReconnectConfig};
+
+#[tokio::test]
+async fn reconnects_and_restores_subscriptions() -> Result<(), EslError> {
+    let server = MockServer::bind().await;
+    let addr = server.addr();
+    let config = ReconnectConfig::default().initial_delay(Duration::from_millis(10));
+    let (inbound, mut session) = tokio::join!(
+        Esl::inbound_with_reconnect(addr, "ClueCon", config),
+        server.accept_inbound()
+    );
+    let inbound = inbound?;
+    let mut states = inbound.state_changes();
+
+    let subscribe = inbound.subscribe(vec!["CHANNEL_CREATE"]);
+    let reply = async {
+        assert_eq!("event json CHANNEL_CREATE", session.read_command().await);
+        session.reply("+OK event listener enabled json").await;
+    };
+    let (response, _) = tokio::join!(subscribe, reply);
+    response?;
+
+    // command in flight while socket drops fails with distinct error
+    let api = inbound.api("status");
+    let drop_connection = async {
+        assert_eq!("api status", session.read_command().await);
+        session.close().await;
+    };
+    let (response, _) = tokio::join!(api, drop_connection);
+    assert_eq!(Err(EslError::ConnectionLost), response);
+
+    states.changed().await.unwrap();
+    assert!(matches!(
+        *states.borrow(),
+        ConnectionState::Reconnecting { .. }
+    ));
+    assert_eq!(Err(EslError::ConnectionLost), inbound.api("status").await);
+
+    let mut session = server.accept().await;
+    session.send("Content-Type: auth/request\n\n").await;
+    assert_eq!("auth ClueCon", session.read_command().await);
+    session.reply("+OK accepted").await;
+    assert_eq!(
+        "event json BACKGROUND_JOB CHANNEL_EXECUTE_COMPLETE CHANNEL_CREATE",
+        session.read_command().await
+    );
+    session.reply("+OK event listener enabled json").await;
+
+    while *states.borrow_and_update() != ConnectionState::Connected {
+        states.changed().await.unwrap();
+    }
+    assert!(inbound.connected());
+
+    let api = inbound.api("status");
+    let reply = async {
+        assert_eq!("api status", session.read_command().await);
+        session.api_response("+OK UP\n").await;
+    };
+    let (response, _) = tokio::join!(api, reply);
+    assert_eq!(Ok("UP".into()), response);
+    Ok(())
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() -> Result<(), EslError> {
+    let server = MockServer::bind().await;
+    let addr = server.addr();
+    let config = ReconnectConfig::default()
+        .initial_delay(Duration::from_millis(10))
+        .max_attempts(2);
+    let (inbound, session) = tokio::join!(
+        Esl::inbound_with_reconnect(addr, "ClueCon", config),
+        server.accept_inbound()
+    );
+    let inbound = inbound?;
+    let mut states = inbound.state_changes();
+    drop(server);
+    session.close().await;
+
+    while *states.borrow_and_update() != ConnectionState::Disconnected {
+        states.changed().await.unwrap();
+    }
+    assert!(!inbound.connected());
+    Ok(())
+}
+
+#[tokio::test]
+async fn handles_fail_once_primary_connection_dropped() -> Result<(), EslError> {
+    let server = MockServer::bind().await;
+    let addr = server.addr();
+    let (inbound, _session) = tokio::join!(
+        Esl::inbound_with_reconnect(addr, "ClueCon", ReconnectConfig::default()),
+        server.accept_inbound()
+    );
+    let inbound = inbound?;
+    let mut states = inbound.state_changes();
+    let conference = inbound.conference("room");
+    drop(inbound);
+
+    let disconnected = states.wait_for(|state| *state == ConnectionState::Disconnected);
+    assert!(tokio::time::timeout(Duration::from_secs(1), disconnected)
+        .await
+        .is_ok());
+    let lock = tokio::time::timeout(Duration::from_secs(1), conference.lock()).await;
+    assert_eq!(Ok(Err(EslError::Disconnected)), lock);
+    Ok(())
+}
+
+#[tokio::test]
+async fn rejected_resubscription_fails_reconnect_attempt() -> Result<(), EslError> {
+    let server = MockServer::bind().await;
+    let addr = server.addr();
+    let config = ReconnectConfig::default().initial_delay(Duration::from_millis(10));
+    let (inbound, session) = tokio::join!(
+        Esl::inbound_with_reconnect(addr, "ClueCon", config),
+        server.accept_inbound()
+    );
+    let inbound = inbound?;
+    let mut states = inbound.state_changes();
+    session.close().await;
+
+    let mut session = server.accept().await;
+    session.send("Content-Type: auth/request\n\n").await;
+    session.expect("auth ClueCon", "+OK accepted").await;
+    session
+        .expect(
+            "event json BACKGROUND_JOB CHANNEL_EXECUTE_COMPLETE",
+            "-ERR no keywords supplied",
+        )
+        .await;
+
+    let mut session = server.accept().await;
+    assert!(!inbound.connected());
+    assert!(matches!(
+        inbound.connection_state(),
+        ConnectionState::Reconnecting { .. }
+    ));
+    session.send("Content-Type: auth/request\n\n").await;
+    session.expect("auth ClueCon", "+OK accepted").await;
+    assert!(!inbound.connected());
+    session
+        .expect(
+            "event json BACKGROUND_JOB CHANNEL_EXECUTE_COMPLETE",
+            "+OK event listener enabled json",
+        )
+        .await;
+
+    states
+        .wait_for(|state| *state == ConnectionState::Connected)
+        .await
+        .unwrap();
+    assert!(inbound.connected());
+    Ok(())
+}
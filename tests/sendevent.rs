@@ -0,0 +1,57 @@
+mod common;
+
+use common::connect_inbound;
+use freeswitch_esl::{EslError, EventBuilder, EventName};
+
+#[tokio::test]
+async fn events_are_sent_with_headers_and_body() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let body = "Messages-Waiting: yes\nVoice-Message: 1/0 (0/0)\n";
+    let notify = EventBuilder::new(EventName::Notify)
+        .header("profile", "internal")
+        .header("event-string", "message-summary")
+        .header("user", "1000")
+        .header("host", "example.com")
+        .header("content-type", "application/simple-message-summary")
+        .body(body)
+        .build();
+    let custom = EventBuilder::custom("my::event")
+        .header("Job", "42")
+        .build();
+
+    let sent = async {
+        inbound.sendevent(&notify).await?;
+        inbound.sendevent(&custom).await
+    };
+    let reply = async {
+        assert_eq!(
+            format!(
+                "sendevent NOTIFY\nEvent-Name: NOTIFY\ncontent-type: application/simple-message-summary\nevent-string: message-summary\nhost: example.com\nprofile: internal\nuser: 1000\nContent-Length: {}",
+                body.len()
+            ),
+            session.read_command().await
+        );
+        assert_eq!(body, session.read_body(body.len()).await);
+        session
+            .reply("+OK 6b8fd0a1-7f3c-4b46-9c5e-5d0b3a3b1f10")
+            .await;
+        session
+            .expect(
+                "sendevent CUSTOM\nEvent-Name: CUSTOM\nEvent-Subclass: my::event\nJob: 42",
+                "+OK 0ae1f7f0-1b4c-4d8e-8f4e-43c4b1f2a9d3",
+            )
+            .await;
+    };
+    let (sent, _) = tokio::join!(sent, reply);
+    sent?;
+
+    let invalid = EventBuilder::new(EventName::Message)
+        .header("body", "two\nlines")
+        .build();
+    assert!(matches!(
+        inbound.sendevent(&invalid).await,
+        Err(EslError::InvalidEvent(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::connect_inbound;
+use freeswitch_esl::{EslError, HangupCause, SendMsg};
+
+#[tokio::test]
+async fn call_commands_are_sent_to_channel() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    let hangup = SendMsg::hangup(HangupCause::UserBusy);
+    assert_eq!(Err(EslError::NoCallUuid), inbound.sendmsg(&hangup).await);
+
+    let call = inbound.call("call-1");
+    let transfer = SendMsg::xferext()
+        .application("answer", "")
+        .application("playback", "ivr/ivr-welcome.wav");
+    let unicast = SendMsg::unicast(
+        "127.0.0.1:8025".parse().unwrap(),
+        "127.0.0.1:8026".parse().unwrap(),
+    )
+    .transport("tcp")
+    .flags("native")
+    .uuid("call-2");
+    let body = "hello\nworld\n";
+    let chat = SendMsg::new("execute")
+        .header("execute-app-name", "send_info")
+        .body("text/plain", body);
+
+    let sent = async {
+        call.sendmsg(&hangup).await?;
+        call.sendmsg(&transfer).await?;
+        call.sendmsg(&unicast).await?;
+        call.sendmsg(&chat).await
+    };
+    let reply = async {
+        session
+            .expect(
+                "sendmsg call-1\ncall-command: hangup\nhangup-cause: USER_BUSY",
+                "+OK",
+            )
+            .await;
+        session
+            .expect(
+                "sendmsg call-1\ncall-command: xferext\napplication: answer\napplication: playback ivr/ivr-welcome.wav",
+                "+OK",
+            )
+            .await;
+        session
+            .expect(
+                "sendmsg call-2\ncall-command: unicast\nlocal-ip: 127.0.0.1\nlocal-port: 8025\nremote-ip: 127.0.0.1\nremote-port: 8026\ntransport: tcp\nflags: native",
+                "+OK",
+            )
+            .await;
+        assert_eq!(
+            format!(
+                "sendmsg call-1\ncall-command: execute\nexecute-app-name: send_info\ncontent-type: text/plain\nContent-Length: {}",
+                body.len()
+            ),
+            session.read_command().await
+        );
+        assert_eq!(body, session.read_body(body.len()).await);
+        session.reply("-ERR invalid session id [call-1]").await;
+    };
+    let (sent, _) = tokio::join!(sent, reply);
+    assert_eq!(
+        Err(EslError::ApiError("invalid session id [call-1]".into())),
+        sent
+    );
+    Ok(())
+}
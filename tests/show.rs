@@ -0,0 +1,59 @@
+mod common;
+
+use common::connect_inbound;
+use freeswitch_esl::{parse_show, ChannelRow, EslError, ModuleRow, RegistrationRow};
+
+#[test]
+fn delimited_output() -> Result<(), EslError> {
+    let output = "type,name,ikey,filename\napi,status,mod_commands,/usr/lib/freeswitch/mod/mod_commands.so\n\n1 total.\n";
+    let modules: Vec<ModuleRow> = parse_show(output)?;
+    assert_eq!(1, modules.len());
+    assert_eq!("api", modules[0].module_type);
+    assert_eq!("status", modules[0].name);
+    assert_eq!("mod_commands", modules[0].ikey);
+
+    let registrations: Vec<RegistrationRow> = parse_show("\n0 total.\n")?;
+    assert!(registrations.is_empty());
+
+    let result = parse_show::<ModuleRow>("type,name\napi,status,extra\n\n1 total.\n");
+    assert!(matches!(result, Err(EslError::ProtocolError(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn show_channels_as_json() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let channels = inbound.show_channels();
+    let reply = async {
+        assert_eq!("api show channels as json", session.read_command().await);
+        session
+            .api_response(concat!(r#"{"row_count":1,"rows":[{"uuid":"abc","direction":"inbound","cid_num":"1000","application_data":"a,b","callstate":"ACTIVE"}]}"#, "\n"))
+            .await;
+    };
+    let (channels, _) = tokio::join!(channels, reply);
+    let channels = channels?;
+    assert_eq!(
+        vec![ChannelRow {
+            uuid: "abc".into(),
+            direction: "inbound".into(),
+            cid_num: "1000".into(),
+            application_data: "a,b".into(),
+            callstate: "ACTIVE".into(),
+            ..Default::default()
+        }],
+        channels
+    );
+
+    let registrations = inbound.show_registrations();
+    let reply = async {
+        assert_eq!(
+            "api show registrations as json",
+            session.read_command().await
+        );
+        session.api_response("{\"row_count\":0}\n").await;
+    };
+    let (registrations, _) = tokio::join!(registrations, reply);
+    assert!(registrations?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use std::time::Duration;
+
+use common::connect_inbound;
+use freeswitch_esl::{
+    parse_gateway_status, parse_profile_status, parse_sofia_registrations, parse_sofia_status,
+    EslError, GatewayState,
+};
+
+const SOFIA_STATUS: &str =
+    "                     Name\t   Type\t                                      Data\tState
+=================================================================================================
+                 external\tprofile\t          sip:mod_sofia@192.168.1.10:5080\tRUNNING (2)
+    external::example.com\tgateway\t                    sip:joeuser@example.com\tFAIL_WAIT
+             192.168.1.10\t  alias\t                                  internal\tALIASED
+                 internal\tprofile\t          sip:mod_sofia@192.168.1.10:5060\tRUNNING (0)
+                 internal\tprofile\t         sips:mod_sofia@192.168.1.10:5061\tRUNNING (0) (TLS)
+=================================================================================================
+2 profiles 1 alias
+";
+
+#[test]
+fn sofia_status_output() -> Result<(), EslError> {
+    let status = parse_sofia_status(SOFIA_STATUS)?;
+    assert_eq!(3, status.profiles.len());
+    assert_eq!("sips:mod_sofia@192.168.1.10:5061", status.profiles[2].url);
+    assert_eq!("RUNNING", status.profiles[2].state);
+    assert_eq!("external", status.profiles[0].name);
+    assert_eq!("RUNNING", status.profiles[0].state);
+    assert_eq!(2, status.profiles[0].calls);
+    assert_eq!("external", status.gateways[0].profile);
+    assert_eq!("example.com", status.gateways[0].name);
+    assert_eq!(GatewayState::FailWait, status.gateways[0].state);
+    assert_eq!(
+        vec![("192.168.1.10".to_string(), "internal".to_string())],
+        status.aliases
+    );
+
+    let profile = parse_profile_status(
+        "=================================================================================================\nName             \tinternal\nURL              \tsip:mod_sofia@192.168.1.10:5060\nCALLS-IN         \t12\nFAILED-CALLS-IN  \t1\nCALLS-OUT        \t3\nFAILED-CALLS-OUT \t0\n=================================================================================================\n",
+    )?;
+    assert_eq!("internal", profile.name);
+    assert_eq!(12, profile.calls_in);
+    assert_eq!(1, profile.failed_calls_in);
+    assert_eq!(3, profile.calls_out);
+    assert_eq!(
+        Some("sip:mod_sofia@192.168.1.10:5060"),
+        profile.settings.get("URL").map(String::as_str)
+    );
+
+    let gateway = parse_gateway_status(
+        "=================================================================================================\nName    \texample.com\nProfile \texternal\nProxy   \tsip:example.com\nState   \tREGED\nStatus  \tUP\n=================================================================================================\n",
+    )?;
+    assert_eq!("example.com", gateway.name);
+    assert_eq!(GatewayState::Reged, gateway.state);
+    assert_eq!("UP", gateway.status);
+    assert_eq!(
+        Err(EslError::ApiError("Invalid Gateway!".into())),
+        parse_gateway_status("Invalid Gateway!\n")
+    );
+
+    let registrations = parse_sofia_registrations(
+        "Registrations:\n=================================================================================================\nCall-ID:    \tabc\nUser:       \t1000@192.168.1.10\nContact:    \t\"1000\" <sip:1000@192.168.1.20:5060>\nIP:         \t192.168.1.20\nPort:       \t5060\n\nCall-ID:    \tdef\nUser:       \t1001@192.168.1.10\n\nTotal items returned: 2\n=================================================================================================\n",
+    );
+    assert_eq!(2, registrations.len());
+    assert_eq!("abc", registrations[0].call_id);
+    assert_eq!(
+        "\"1000\" <sip:1000@192.168.1.20:5060>",
+        registrations[0].contact
+    );
+    assert_eq!("5060", registrations[0].port);
+    assert_eq!("1001@192.168.1.10", registrations[1].user);
+    Ok(())
+}
+
+#[tokio::test]
+async fn gateway_watcher_combines_polling_and_events() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let watcher = inbound.watch_gateways(Duration::from_secs(3600));
+    let reply = async {
+        session
+            .expect(
+                "event json CUSTOM sofia::gateway_state sofia::register",
+                "+OK event listener enabled json",
+            )
+            .await;
+    };
+    let (watcher, _) = tokio::join!(watcher, reply);
+    let mut watcher = watcher?;
+
+    assert_eq!("api sofia status", session.read_command().await);
+    session.api_response(SOFIA_STATUS).await;
+    watcher.changed().await?;
+    assert_eq!(Some(GatewayState::FailWait), watcher.gateway("example.com"));
+
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CUSTOM","Event-Subclass":"sofia::gateway_state","Gateway":"example.com","State":"REGED","Ping-Status":"UP"}"#,
+        )
+        .await;
+    watcher.changed().await?;
+    assert_eq!(Some(GatewayState::Reged), watcher.gateway("example.com"));
+
+    session
+        .event(
+            "text/event-json",
+            r#"{"Event-Name":"CUSTOM","Event-Subclass":"sofia::register","from-user":"1000"}"#,
+        )
+        .await;
+    assert_eq!("api sofia status", session.read_command().await);
+    session.api_response(SOFIA_STATUS).await;
+    watcher.changed().await?;
+    assert_eq!(Some(GatewayState::FailWait), watcher.gateway("example.com"));
+
+    session.close().await;
+    assert_eq!(Err(EslError::Disconnected), watcher.changed().await);
+    Ok(())
+}
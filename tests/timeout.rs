@@ -0,0 +1,63 @@
+mod common;
+
+use std::time::Duration;
+
+use common::connect_inbound;
+use freeswitch_esl::EslError;
+
+#[tokio::test]
+async fn timed_out_command_does_not_shift_replies() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let slow = inbound.api_with_timeout("slow", Duration::from_millis(50));
+    let read = async {
+        assert_eq!("api slow", session.read_command().await);
+    };
+    let (response, _) = tokio::join!(slow, read);
+    assert_eq!(Err(EslError::Timeout), response);
+
+    let fast = inbound.api("fast");
+    let reply = async {
+        assert_eq!("api fast", session.read_command().await);
+        session.api_response("+OK slow\n").await;
+        session.api_response("+OK fast\n").await;
+    };
+    let (response, _) = tokio::join!(fast, reply);
+    assert_eq!(Ok("fast".into()), response);
+    Ok(())
+}
+
+#[tokio::test]
+async fn dropped_command_does_not_shift_replies() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+
+    let dropped = tokio::time::timeout(Duration::from_millis(1), inbound.api("dropped"));
+    assert!(dropped.await.is_err());
+
+    let second = inbound.api("second");
+    let reply = async {
+        assert_eq!("api dropped", session.read_command().await);
+        assert_eq!("api second", session.read_command().await);
+        session.api_response("+OK dropped\n").await;
+        session.api_response("+OK second\n").await;
+    };
+    let (response, _) = tokio::join!(second, reply);
+    assert_eq!(Ok("second".into()), response);
+    Ok(())
+}
+
+#[tokio::test]
+async fn default_timeout_applies_to_bgapi() -> Result<(), EslError> {
+    let (inbound, mut session) = connect_inbound().await;
+    inbound.set_default_timeout(Some(Duration::from_millis(50)));
+
+    let bgapi = inbound.bgapi("originate user/1000 &park");
+    let reply = async {
+        let command = session.read_command().await;
+        assert!(command.starts_with("bgapi originate user/1000 &park\nJob-UUID: "));
+        session.reply("+OK Job-UUID").await;
+    };
+    let (response, _) = tokio::join!(bgapi, reply);
+    assert_eq!(Err(EslError::Timeout), response);
+    Ok(())
+}